@@ -0,0 +1,311 @@
+use core::marker::PhantomData;
+use std::{collections::BTreeMap, sync::Arc};
+
+use bevy::{
+    asset::{AssetLoadError, AssetLoadFailedEvent, RecursiveDependencyLoadState},
+    prelude::*,
+};
+
+use super::{
+    AssetLoadFailed, AssetLoadFailure, AssetLoadFailurePolicy, AssetManifest,
+    failure::AssetLoadFailureAction, manifest::AssetManifestHandle,
+};
+
+/// A group of assets listed in the [`AssetManifest`] that is loaded and tracked as a whole.
+pub trait AssetCategory: Send + Sync + 'static {
+    type Asset: Asset;
+
+    /// Name used to report this category's loading progress.
+    const NAME: &'static str;
+
+    /// Returns this category's entries in the manifest, as logical names mapped to asset paths.
+    fn entries(manifest: &AssetManifest) -> &BTreeMap<String, String>;
+}
+
+pub trait AssetCollectionAppExt {
+    fn add_asset_collection<C: AssetCategory>(&mut self) -> &mut Self;
+}
+
+impl AssetCollectionAppExt for App {
+    fn add_asset_collection<C: AssetCategory>(&mut self) -> &mut Self {
+        self.init_resource::<AssetCollectionLoadState<C>>();
+        self.init_resource::<AssetsLoadProgress>();
+        self.world_mut()
+            .resource_mut::<AssetsLoadProgress>()
+            .categories
+            .insert(C::NAME, CategoryProgress::default());
+        self.add_systems(
+            Update,
+            (
+                insert_asset_collection::<C>.run_if(not(resource_exists::<AssetCollection<C>>)),
+                update_asset_collection_load_state::<C>.run_if(
+                    resource_exists::<AssetCollection<C>>
+                        .and(not(AssetCollectionLoadState::<C>::loaded)),
+                ),
+            )
+                .chain(),
+        )
+    }
+}
+
+/// Handles to every asset of a category, keyed by the logical name given in the manifest.
+#[derive(Resource)]
+pub struct AssetCollection<C: AssetCategory> {
+    handles: BTreeMap<String, Handle<C::Asset>>,
+}
+
+impl<C: AssetCategory> AssetCollection<C> {
+    pub fn get(&self, name: &str) -> Option<Handle<C::Asset>> {
+        self.handles.get(name).cloned()
+    }
+
+    fn name_of(&self, id: AssetId<C::Asset>) -> Option<&str> {
+        self.handles
+            .iter()
+            .find(|(_, handle)| handle.id() == id)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+#[derive(Resource, Deref)]
+pub struct AssetCollectionLoadState<C: AssetCategory> {
+    #[deref]
+    state: RecursiveDependencyLoadState,
+    loaded: usize,
+    total: usize,
+    failures: Vec<AssetLoadFailure>,
+    attempts: BTreeMap<String, u32>,
+    dependency_errors: BTreeMap<String, Arc<AssetLoadError>>,
+    _marker: PhantomData<C>,
+}
+
+impl<C: AssetCategory> AssetCollectionLoadState<C> {
+    pub fn loaded(load_state: Res<Self>) -> bool {
+        load_state.is_loaded()
+    }
+
+    /// Returns the fraction of this category's assets loaded so far, from `0.0` to `1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.is_loaded() {
+            1.0
+        } else if self.total == 0 {
+            0.0
+        } else {
+            self.loaded as f32 / self.total as f32
+        }
+    }
+
+    /// Returns the assets in this category that failed to load and are no longer being waited on.
+    pub fn failures(&self) -> &[AssetLoadFailure] {
+        &self.failures
+    }
+
+    fn has_failed(&self, name: &str) -> bool {
+        self.failures.iter().any(|failure| failure.name == name)
+    }
+}
+
+impl<C: AssetCategory> Default for AssetCollectionLoadState<C> {
+    fn default() -> Self {
+        Self {
+            state: RecursiveDependencyLoadState::NotLoaded,
+            loaded: 0,
+            total: 0,
+            failures: Vec::new(),
+            attempts: BTreeMap::new(),
+            dependency_errors: BTreeMap::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Aggregated loading progress of every registered [`AssetCategory`].
+#[derive(Resource, Default)]
+pub struct AssetsLoadProgress {
+    categories: BTreeMap<&'static str, CategoryProgress>,
+}
+
+#[derive(Default)]
+struct CategoryProgress {
+    settled: usize,
+    total: usize,
+    loaded: bool,
+}
+
+impl AssetsLoadProgress {
+    /// Returns `true` once every registered category has finished loading.
+    pub fn loaded(load_progress: Res<Self>) -> bool {
+        load_progress
+            .categories
+            .values()
+            .all(|category| category.loaded)
+    }
+
+    /// Returns the fraction of assets loaded so far, from `0.0` to `1.0`.
+    pub fn fraction(&self) -> f32 {
+        let (settled, total) = self
+            .categories
+            .values()
+            .fold((0, 0), |(settled, total), category| {
+                (settled + category.settled, total + category.total)
+            });
+
+        if total == 0 {
+            0.0
+        } else {
+            settled as f32 / total as f32
+        }
+    }
+}
+
+fn insert_asset_collection<C: AssetCategory>(
+    mut commands: Commands,
+    asset_manifest_handle: Res<AssetManifestHandle>,
+    asset_manifests: Res<Assets<AssetManifest>>,
+    asset_server: Res<AssetServer>,
+) {
+    let Some(asset_manifest) = asset_manifests.get(&**asset_manifest_handle) else {
+        return;
+    };
+
+    let handles = C::entries(asset_manifest)
+        .iter()
+        .map(|(name, path)| (name.clone(), asset_server.load(path.as_str())))
+        .collect();
+
+    commands.insert_resource(AssetCollection::<C> { handles });
+}
+
+fn update_asset_collection_load_state<C: AssetCategory>(
+    mut commands: Commands,
+    mut load_state: ResMut<AssetCollectionLoadState<C>>,
+    mut load_progress: ResMut<AssetsLoadProgress>,
+    mut collection: ResMut<AssetCollection<C>>,
+    mut asset_load_failed_events: MessageReader<AssetLoadFailedEvent<C::Asset>>,
+    mut app_exit: MessageWriter<AppExit>,
+    failure_policy: Res<AssetLoadFailurePolicy>,
+    asset_server: Res<AssetServer>,
+) {
+    let mut failures = asset_load_failed_events
+        .read()
+        .filter_map(|event| {
+            let name = collection.name_of(event.id)?.to_string();
+            Some((name, event.path.clone(), event.error.clone()))
+        })
+        .collect::<Vec<_>>();
+
+    // Only the asset that failed gets a failed event, so assets whose dependencies failed are
+    // picked up from their recursive load state. Every failure comes with a new error, which
+    // tells a fresh failure apart from one that was already handled.
+    for (name, handle) in &collection.handles {
+        if load_state.has_failed(name) || asset_server.load_state(handle.id()).is_failed() {
+            continue;
+        }
+        let RecursiveDependencyLoadState::Failed(error) =
+            asset_server.recursive_dependency_load_state(handle.id())
+        else {
+            continue;
+        };
+        if load_state
+            .dependency_errors
+            .get(name)
+            .is_some_and(|handled| Arc::ptr_eq(handled, &error))
+        {
+            continue;
+        }
+        load_state
+            .dependency_errors
+            .insert(name.clone(), error.clone());
+        if let Some(path) = handle.path() {
+            failures.push((name.clone(), path.clone(), (*error).clone()));
+        }
+    }
+
+    for (name, path, error) in failures {
+        let failure = AssetLoadFailure {
+            name,
+            path: path.to_string(),
+            error,
+        };
+        let attempts = load_state.attempts.entry(failure.name.clone()).or_default();
+        *attempts += 1;
+
+        commands.trigger(AssetLoadFailed {
+            category: C::NAME,
+            failure: failure.clone(),
+        });
+
+        match failure_policy.action(*attempts) {
+            AssetLoadFailureAction::Abort => {
+                error!(
+                    "Aborting, {} failed to load: {}",
+                    failure.path, failure.error
+                );
+                app_exit.write(AppExit::error());
+            }
+            AssetLoadFailureAction::Retry => {
+                warn!(
+                    "Retrying {}, it failed to load: {}",
+                    failure.path, failure.error
+                );
+                asset_server.reload(path);
+                continue;
+            }
+            AssetLoadFailureAction::Placeholder => {
+                warn!(
+                    "Using a placeholder for {}: {}",
+                    failure.path, failure.error
+                );
+                collection
+                    .handles
+                    .insert(failure.name.clone(), Handle::default());
+            }
+            AssetLoadFailureAction::GiveUp => {
+                error!("Giving up on {}: {}", failure.path, failure.error);
+            }
+        }
+
+        load_state.failures.push(failure);
+    }
+
+    let total = collection.handles.len();
+    let loaded = collection
+        .handles
+        .iter()
+        .filter(|(name, handle)| {
+            !load_state.has_failed(name)
+                && asset_server
+                    .recursive_dependency_load_state(handle.id())
+                    .is_loaded()
+        })
+        .count();
+    let settled = loaded + load_state.failures.len();
+    let fatal_error = load_state
+        .failures
+        .first()
+        .filter(|_| {
+            !matches!(
+                *failure_policy,
+                AssetLoadFailurePolicy::ContinueWithPlaceholders
+            )
+        })
+        .map(|failure| Arc::new(failure.error.clone()));
+
+    load_state.loaded = loaded;
+    load_state.total = total;
+    load_state.state = if settled < total {
+        RecursiveDependencyLoadState::Loading
+    } else if let Some(error) = fatal_error {
+        RecursiveDependencyLoadState::Failed(error)
+    } else {
+        RecursiveDependencyLoadState::Loaded
+    };
+    load_progress.categories.insert(
+        C::NAME,
+        CategoryProgress {
+            settled,
+            total,
+            loaded: load_state.is_loaded(),
+        },
+    );
+}
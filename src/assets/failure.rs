@@ -0,0 +1,50 @@
+use bevy::{asset::AssetLoadError, prelude::*};
+
+/// Decides what happens when an asset listed in the manifest fails to load.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub enum AssetLoadFailurePolicy {
+    /// Exits the app with an error as soon as an asset fails to load.
+    Abort,
+    /// Stops waiting for the failed asset and hands out a default handle in its place.
+    #[default]
+    ContinueWithPlaceholders,
+    /// Reloads the failed asset up to `max_attempts` times, after which it's reported as failed
+    /// and its category never finishes loading.
+    Retry { max_attempts: u32 },
+}
+
+pub(super) enum AssetLoadFailureAction {
+    Abort,
+    Placeholder,
+    Retry,
+    GiveUp,
+}
+
+impl AssetLoadFailurePolicy {
+    /// Returns the action to take for an asset that has failed to load `attempts` times.
+    pub(super) fn action(&self, attempts: u32) -> AssetLoadFailureAction {
+        match self {
+            Self::Abort => AssetLoadFailureAction::Abort,
+            Self::ContinueWithPlaceholders => AssetLoadFailureAction::Placeholder,
+            Self::Retry { max_attempts } if attempts <= *max_attempts => {
+                AssetLoadFailureAction::Retry
+            }
+            Self::Retry { .. } => AssetLoadFailureAction::GiveUp,
+        }
+    }
+}
+
+/// Details of an asset from the manifest that failed to load.
+#[derive(Clone, Debug)]
+pub struct AssetLoadFailure {
+    pub name: String,
+    pub path: String,
+    pub error: AssetLoadError,
+}
+
+/// Triggered every time an asset listed in the manifest, or the manifest itself, fails to load.
+#[derive(Event, Clone, Debug)]
+pub struct AssetLoadFailed {
+    pub category: &'static str,
+    pub failure: AssetLoadFailure,
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use bevy::{asset::AssetLoadFailedEvent, prelude::*};
+use serde::Deserialize;
+
+use super::{
+    AssetLoadFailed, AssetLoadFailure, AssetLoadFailurePolicy, failure::AssetLoadFailureAction,
+};
+
+const ASSET_MANIFEST_PATH: &str = "manifest.ron";
+
+/// Lists every asset the game preloads, grouped by category and keyed by logical name.
+///
+/// Paths are relative to the `assets` folder. The same file is used on every platform, so there's
+/// no need to keep a separate list of files for builds that can't read folders, such as wasm.
+#[derive(Asset, TypePath, Deserialize, Default)]
+pub struct AssetManifest {
+    #[serde(default)]
+    pub textures: BTreeMap<String, String>,
+    #[serde(default)]
+    pub fonts: BTreeMap<String, String>,
+    #[serde(default)]
+    pub music: BTreeMap<String, String>,
+    #[serde(default)]
+    pub sfx: BTreeMap<String, String>,
+    #[serde(default)]
+    pub sequences: BTreeMap<String, String>,
+    #[serde(default)]
+    pub catalogs: BTreeMap<String, String>,
+    #[serde(default)]
+    pub poems: BTreeMap<String, String>,
+    #[serde(default)]
+    pub dialogues: BTreeMap<String, String>,
+    #[serde(default)]
+    pub locales: BTreeMap<String, String>,
+    #[serde(default)]
+    pub soundtracks: BTreeMap<String, String>,
+    #[serde(default)]
+    pub cues: BTreeMap<String, String>,
+}
+
+#[derive(Resource, Deref)]
+pub(super) struct AssetManifestHandle(pub Handle<AssetManifest>);
+
+pub(super) fn load_asset_manifest(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AssetManifestHandle(asset_server.load(ASSET_MANIFEST_PATH)));
+}
+
+/// Applies the [`AssetLoadFailurePolicy`] to the manifest itself, since no asset collection can be
+/// populated without it.
+pub(super) fn handle_asset_manifest_load_failure(
+    mut commands: Commands,
+    mut asset_manifest_handle: ResMut<AssetManifestHandle>,
+    mut asset_manifests: ResMut<Assets<AssetManifest>>,
+    mut asset_load_failed_events: MessageReader<AssetLoadFailedEvent<AssetManifest>>,
+    mut app_exit: MessageWriter<AppExit>,
+    mut attempts: Local<u32>,
+    failure_policy: Res<AssetLoadFailurePolicy>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in asset_load_failed_events.read() {
+        if event.id != asset_manifest_handle.id() {
+            continue;
+        }
+
+        let failure = AssetLoadFailure {
+            name: ASSET_MANIFEST_PATH.to_string(),
+            path: event.path.to_string(),
+            error: event.error.clone(),
+        };
+        *attempts += 1;
+
+        match failure_policy.action(*attempts) {
+            AssetLoadFailureAction::Abort => {
+                error!(
+                    "Aborting, the asset manifest failed to load: {}",
+                    failure.error
+                );
+                app_exit.write(AppExit::error());
+            }
+            AssetLoadFailureAction::Retry => {
+                warn!(
+                    "Retrying the asset manifest, it failed to load: {}",
+                    failure.error
+                );
+                asset_server.reload(event.path.clone());
+            }
+            AssetLoadFailureAction::Placeholder => {
+                warn!("Using an empty asset manifest: {}", failure.error);
+                asset_manifest_handle.0 = asset_manifests.add(AssetManifest::default());
+            }
+            AssetLoadFailureAction::GiveUp => {
+                error!("Giving up on the asset manifest: {}", failure.error);
+            }
+        }
+
+        commands.trigger(AssetLoadFailed {
+            category: "manifest",
+            failure,
+        });
+    }
+}
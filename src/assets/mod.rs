@@ -0,0 +1,11 @@
+mod collection;
+mod failure;
+mod manifest;
+mod plugin;
+mod ron_loader;
+
+pub use collection::*;
+pub use failure::{AssetLoadFailed, AssetLoadFailure, AssetLoadFailurePolicy};
+pub use manifest::AssetManifest;
+pub use plugin::*;
+pub use ron_loader::*;
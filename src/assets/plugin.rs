@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use super::{
+    AssetLoadFailurePolicy, AssetManifest, AssetsLoadProgress, RonAssetPlugin,
+    manifest::{handle_asset_manifest_load_failure, load_asset_manifest},
+};
+
+pub struct AssetsPlugin;
+
+impl Plugin for AssetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<AssetManifest>::new(&["manifest.ron"]));
+
+        app.init_resource::<AssetsLoadProgress>();
+        app.init_resource::<AssetLoadFailurePolicy>();
+
+        app.add_systems(Startup, load_asset_manifest);
+        app.add_systems(Update, handle_asset_manifest_load_failure);
+    }
+}
@@ -0,0 +1,398 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_kira_audio::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::Deserialize;
+
+use crate::assets::{
+    AssetCategory, AssetCollection, AssetCollectionAppExt, AssetManifest, RonAssetPlugin,
+};
+
+use super::{
+    AudioBus, BusChannels, PlaybackSettings, SoundEffectHandles, SpatialEmitter,
+    play_audio_with_settings,
+};
+
+const SOUND_CUES_NAME: &str = "main";
+
+pub struct SoundCuesPlugin;
+
+impl Plugin for SoundCuesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<SoundCues>::new(&["cues.ron"]));
+        app.add_asset_collection::<SoundCueSets>();
+
+        app.init_resource::<SoundCuePlayback>();
+
+        app.add_observer(on_play_sound_cue);
+    }
+}
+
+/// A named sound that picks one of several files, and a slightly different pitch and volume, each
+/// time it plays, so repeated sounds like footsteps don't sound mechanical.
+#[derive(Deserialize, Clone, Debug)]
+pub struct SoundCue {
+    pub variants: Vec<SoundVariant>,
+    #[serde(default)]
+    pub selection: VariantSelection,
+    /// Range the playback rate is drawn from, which also shifts the pitch.
+    #[serde(default = "SoundCue::default_playback_rate")]
+    pub playback_rate: (f64, f64),
+    /// Range the volume is drawn from, in decibels.
+    #[serde(default)]
+    pub volume: (f32, f32),
+    /// Seconds after playing during which the cue ignores requests to play again.
+    #[serde(default)]
+    pub cooldown_secs: f32,
+    /// Most instances of the cue that may play at once. Requests past it are dropped.
+    #[serde(default)]
+    pub max_instances: Option<usize>,
+    /// Bus whose volume the cue follows, such as `Voice` for the raven's caws.
+    #[serde(default = "SoundCue::default_bus")]
+    pub bus: AudioBus,
+}
+
+impl SoundCue {
+    fn default_playback_rate() -> (f64, f64) {
+        (1.0, 1.0)
+    }
+
+    fn default_bus() -> AudioBus {
+        AudioBus::Sfx
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct SoundVariant {
+    /// Name of the file in the asset manifest's sfx.
+    pub sfx: String,
+    /// How likely the variant is to be picked, relative to the others.
+    #[serde(default = "SoundVariant::default_weight")]
+    pub weight: f32,
+}
+
+impl SoundVariant {
+    fn default_weight() -> f32 {
+        1.0
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Default, Debug)]
+pub enum VariantSelection {
+    /// Picks any variant, by weight.
+    #[default]
+    Weighted,
+    /// Picks by weight among the variants other than the one played last.
+    NoRepeat,
+}
+
+#[derive(Asset, TypePath, Deserialize, Default)]
+pub struct SoundCues {
+    #[serde(default)]
+    pub cues: BTreeMap<String, SoundCue>,
+}
+
+pub struct SoundCueSets;
+
+impl AssetCategory for SoundCueSets {
+    type Asset = SoundCues;
+
+    const NAME: &'static str = "cues";
+
+    fn entries(manifest: &AssetManifest) -> &BTreeMap<String, String> {
+        &manifest.cues
+    }
+}
+
+pub type SoundCueHandles = AssetCollection<SoundCueSets>;
+
+#[derive(Event)]
+pub struct PlaySoundCue {
+    name: String,
+    emitter: Option<Entity>,
+}
+
+impl PlaySoundCue {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            emitter: None,
+        }
+    }
+
+    pub fn with_emitter(mut self, emitter: Entity) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+}
+
+/// What each cue played recently, which its cooldown, instance cap and no-repeat selection depend
+/// on.
+#[derive(Resource)]
+struct SoundCuePlayback {
+    cues: BTreeMap<String, CuePlayback>,
+    /// Sound variation doesn't need to follow the run's seed, so it has a generator of its own.
+    rng: ChaCha8Rng,
+}
+
+impl Default for SoundCuePlayback {
+    fn default() -> Self {
+        Self {
+            cues: BTreeMap::new(),
+            rng: ChaCha8Rng::seed_from_u64(0),
+        }
+    }
+}
+
+#[derive(Default)]
+struct CuePlayback {
+    last_variant: Option<usize>,
+    last_played: Option<Duration>,
+    instances: Vec<Handle<AudioInstance>>,
+}
+
+#[derive(SystemParam)]
+struct SoundCueLibrary<'w> {
+    sound_cue_handles: Res<'w, SoundCueHandles>,
+    sound_cues: Res<'w, Assets<SoundCues>>,
+}
+
+impl SoundCueLibrary<'_> {
+    fn get(&self, name: &str) -> Option<&SoundCue> {
+        self.sound_cue_handles
+            .get(SOUND_CUES_NAME)
+            .and_then(|handle| self.sound_cues.get(&handle))
+            .and_then(|sound_cues| sound_cues.cues.get(name))
+    }
+}
+
+fn on_play_sound_cue(
+    play_cue: On<PlaySoundCue>,
+    sound_cue_library: SoundCueLibrary,
+    mut playback: ResMut<SoundCuePlayback>,
+    mut spatial_emitters: Query<&mut SpatialEmitter>,
+    sound_effect_handles: Res<SoundEffectHandles>,
+    bus_channels: BusChannels,
+    time: Res<Time<Real>>,
+) {
+    let event = play_cue.event();
+    let Some(cue) = sound_cue_library.get(&event.name) else {
+        debug!("No sound cue \"{}\"", event.name);
+        return;
+    };
+
+    let SoundCuePlayback { cues, rng } = playback.as_mut();
+    let cue_playback = cues.entry(event.name.clone()).or_default();
+    let now = time.elapsed();
+
+    if cue_playback.last_played.is_some_and(|last_played| {
+        now.saturating_sub(last_played) < Duration::from_secs_f32(cue.cooldown_secs)
+    }) {
+        return;
+    }
+
+    // Instances that are still waiting for their file count as playing.
+    cue_playback.instances.retain(|instance| {
+        !matches!(
+            bus_channels.state(cue.bus, instance),
+            PlaybackState::Stopped
+        )
+    });
+
+    if cue
+        .max_instances
+        .is_some_and(|max_instances| cue_playback.instances.len() >= max_instances)
+    {
+        return;
+    }
+
+    let excluded = match cue.selection {
+        VariantSelection::Weighted => None,
+        VariantSelection::NoRepeat => cue_playback.last_variant,
+    };
+    let Some(variant_index) = pick_variant(rng, &cue.variants, excluded) else {
+        return;
+    };
+
+    let settings = PlaybackSettings {
+        emitter: event.emitter,
+        playback_rate: draw_in_range(rng, cue.playback_rate),
+        volume: draw_in_range(rng, cue.volume),
+        ..default()
+    };
+    let mut play_audio_command = bus_channels.play(
+        cue.bus,
+        sound_effect_handles
+            .get(&cue.variants[variant_index].sfx)
+            .unwrap_or_default(),
+    );
+    play_audio_with_settings(
+        &mut play_audio_command,
+        &settings,
+        event
+            .emitter
+            .and_then(|entity| spatial_emitters.get_mut(entity).ok()),
+    );
+
+    cue_playback.instances.push(play_audio_command.handle());
+    cue_playback.last_variant = Some(variant_index);
+    cue_playback.last_played = Some(now);
+}
+
+/// Picks a variant by weight, skipping `excluded` unless there's nothing else to pick.
+fn pick_variant(
+    rng: &mut impl Rng,
+    variants: &[SoundVariant],
+    excluded: Option<usize>,
+) -> Option<usize> {
+    let excluded = excluded.filter(|_| variants.len() > 1);
+    let weight = |index: usize| {
+        if Some(index) == excluded {
+            0.0
+        } else {
+            variants[index].weight.max(0.0)
+        }
+    };
+    let total: f32 = (0..variants.len()).map(weight).sum();
+
+    if total <= 0.0 {
+        return excluded.and_then(|_| pick_variant(rng, variants, None));
+    }
+
+    let mut remaining = rng.random_range(0.0..total);
+
+    (0..variants.len())
+        .find(|&index| {
+            remaining -= weight(index);
+            remaining < 0.0
+        })
+        // Rounding may leave a sliver past the last variant.
+        .or_else(|| (0..variants.len()).rev().find(|&index| weight(index) > 0.0))
+}
+
+fn draw_in_range<T>(rng: &mut impl Rng, (start, end): (T, T)) -> T
+where
+    T: PartialOrd + rand::distr::uniform::SampleUniform + Copy,
+{
+    if start < end {
+        rng.random_range(start..=end)
+    } else {
+        start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variants(weights: &[f32]) -> Vec<SoundVariant> {
+        weights
+            .iter()
+            .enumerate()
+            .map(|(index, weight)| SoundVariant {
+                sfx: format!("step_{index}"),
+                weight: *weight,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn no_repeat_never_picks_the_last_variant_twice() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let variants = variants(&[1.0, 5.0, 1.0]);
+        let mut last = None;
+
+        for _ in 0..200 {
+            let picked = pick_variant(&mut rng, &variants, last).unwrap();
+            assert_ne!(Some(picked), last);
+            last = Some(picked);
+        }
+    }
+
+    #[test]
+    fn zero_weights_are_never_picked() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let variants = variants(&[0.0, 1.0, 0.0, 2.0, -1.0]);
+
+        for _ in 0..200 {
+            let picked = pick_variant(&mut rng, &variants, None).unwrap();
+            assert!(matches!(picked, 1 | 3), "picked variant {picked}");
+        }
+    }
+
+    #[test]
+    fn single_variant_is_picked_even_without_repeats() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let variants = variants(&[1.0]);
+
+        assert_eq!(pick_variant(&mut rng, &variants, Some(0)), Some(0));
+        assert_eq!(pick_variant(&mut rng, &variants, None), Some(0));
+    }
+
+    #[test]
+    fn excluded_variant_is_picked_when_nothing_else_can_be() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let one_weighted = variants(&[1.0, 0.0]);
+
+        assert_eq!(pick_variant(&mut rng, &one_weighted, Some(0)), Some(0));
+        assert_eq!(pick_variant(&mut rng, &variants(&[0.0, 0.0]), None), None);
+        assert_eq!(pick_variant(&mut rng, &[], None), None);
+    }
+
+    #[test]
+    fn draws_are_pinned_for_a_fixed_seed() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let variants = variants(&[1.0, 2.0, 3.0]);
+
+        let weighted = (0..8)
+            .map(|_| pick_variant(&mut rng, &variants, None).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(weighted, [0, 0, 1, 1, 1, 2, 0, 2]);
+
+        let mut last = None;
+        let no_repeat = (0..8)
+            .map(|_| {
+                let picked = pick_variant(&mut rng, &variants, last).unwrap();
+                last = Some(picked);
+                picked
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(no_repeat, [0, 2, 1, 2, 0, 1, 2, 1]);
+
+        let playback_rates = (0..3)
+            .map(|_| draw_in_range(&mut rng, (0.9, 1.1)))
+            .collect::<Vec<f64>>();
+        let volumes = (0..3)
+            .map(|_| draw_in_range(&mut rng, (-6.0, 0.0)))
+            .collect::<Vec<f32>>();
+
+        for (drawn, pinned) in playback_rates
+            .iter()
+            .zip([0.972_892_8, 1.097_994_5, 0.940_047_5])
+        {
+            assert!(
+                (drawn - pinned).abs() < 1e-6,
+                "drew {drawn}, pinned {pinned}"
+            );
+        }
+        for (drawn, pinned) in volumes
+            .iter()
+            .zip([-1.579_965_6, -3.694_350_2, -2.739_239_2])
+        {
+            assert!(
+                (drawn - pinned).abs() < 1e-5,
+                "drew {drawn}, pinned {pinned}"
+            );
+        }
+    }
+
+    #[test]
+    fn empty_ranges_draw_their_start() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+
+        assert_eq!(draw_in_range(&mut rng, (1.0, 1.0)), 1.0);
+        assert_eq!(draw_in_range(&mut rng, (-3.0_f32, -6.0)), -3.0);
+    }
+}
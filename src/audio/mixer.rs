@@ -0,0 +1,334 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_kira_audio::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{AmbienceChannel, BgmChannel, VoiceChannel, gain_to_decibels};
+
+/// How long channels take to reach a new volume, so dragging a slider or pausing doesn't click.
+const MIXER_FADE_SECS: f32 = 0.2;
+/// Gain the music bus is scaled by while ducked, about -12 dB.
+const DUCKED_MUSIC_GAIN: f32 = 0.25;
+/// How long the mixer has to stay untouched before it's saved, so dragging a slider doesn't write
+/// on every frame.
+const SAVE_DELAY_SECS: f32 = 1.0;
+
+pub struct MixerPlugin;
+
+impl Plugin for MixerPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AudioMixer::load());
+        app.init_resource::<MusicDucked>();
+        app.init_resource::<DynamicChannelBuses>();
+
+        app.add_systems(
+            Update,
+            (
+                apply_mixer
+                    .run_if(resource_changed::<AudioMixer>.or(resource_changed::<MusicDucked>)),
+                save_mixer,
+            ),
+        );
+    }
+}
+
+/// A group of channels whose volume is set together. Every bus is also scaled by
+/// [`AudioBus::Master`].
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum AudioBus {
+    Master,
+    Music,
+    Sfx,
+    Ambience,
+    Voice,
+}
+
+impl AudioBus {
+    pub const ALL: [Self; 5] = [
+        Self::Master,
+        Self::Music,
+        Self::Sfx,
+        Self::Ambience,
+        Self::Voice,
+    ];
+
+    /// Key of the bus' name in the locale files.
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Self::Master => "settings.bus.master",
+            Self::Music => "settings.bus.music",
+            Self::Sfx => "settings.bus.sfx",
+            Self::Ambience => "settings.bus.ambience",
+            Self::Voice => "settings.bus.voice",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct BusSettings {
+    /// Between 0 and 1.
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl Default for BusSettings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+        }
+    }
+}
+
+/// Volume of every [`AudioBus`], applied to the channels routed through them. Saved whenever it
+/// changes and loaded on startup.
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct AudioMixer {
+    buses: BTreeMap<AudioBus, BusSettings>,
+}
+
+impl AudioMixer {
+    pub fn bus(&self, bus: AudioBus) -> BusSettings {
+        self.buses.get(&bus).copied().unwrap_or_default()
+    }
+
+    pub fn set_volume(&mut self, bus: AudioBus, volume: f32) {
+        self.buses.entry(bus).or_default().volume = volume.clamp(0.0, 1.0);
+    }
+
+    pub fn toggle_mute(&mut self, bus: AudioBus) {
+        let settings = self.buses.entry(bus).or_default();
+        settings.muted = !settings.muted;
+    }
+
+    /// Gain the settings give the channels routed through `bus`, between 0 and 1.
+    pub fn gain(&self, bus: AudioBus) -> f32 {
+        let bus_gain = |bus| {
+            let settings = self.bus(bus);
+            if settings.muted { 0.0 } else { settings.volume }
+        };
+        let gain = match bus {
+            AudioBus::Master => 1.0,
+            bus => bus_gain(bus),
+        };
+
+        gain * bus_gain(AudioBus::Master)
+    }
+
+    /// Loads the saved mixer, falling back to full volume when there's none or it can't be read.
+    fn load() -> Self {
+        storage::read()
+            .and_then(|saved| {
+                ron::from_str(&saved)
+                    .inspect_err(|error| warn!("Couldn't read the saved mixer: {error}"))
+                    .ok()
+            })
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        match ron::to_string(self) {
+            Ok(serialized) => storage::write(&serialized),
+            Err(error) => warn!("Couldn't save the mixer: {error}"),
+        }
+    }
+}
+
+/// Lowers the music while the game is paused. Kept apart from the [`AudioMixer`] since it isn't a
+/// setting, and changing it shouldn't save the mixer.
+#[derive(Resource, Default)]
+pub struct MusicDucked(pub bool);
+
+/// The mixer along with whatever else lowers the buses for now, which together give each channel
+/// its volume.
+#[derive(SystemParam)]
+pub(super) struct MixerGains<'w> {
+    mixer: Res<'w, AudioMixer>,
+    music_ducked: Res<'w, MusicDucked>,
+}
+
+impl MixerGains<'_> {
+    /// Gain of the channels routed through `bus`, between 0 and 1.
+    fn gain(&self, bus: AudioBus) -> f32 {
+        let gain = self.mixer.gain(bus);
+
+        if bus == AudioBus::Music && self.music_ducked.0 {
+            gain * DUCKED_MUSIC_GAIN
+        } else {
+            gain
+        }
+    }
+}
+
+/// The bus each dynamic channel is routed through. Channels that aren't in here play at full
+/// volume.
+#[derive(Resource, Default)]
+pub(super) struct DynamicChannelBuses(HashMap<String, AudioBus>);
+
+/// Gets the dynamic channel called `name`, creating it if needed. New channels are routed through
+/// `bus` and start at its volume, so their first sound doesn't play at full volume.
+pub(super) fn mixed_channel<'a>(
+    dynamic_audio_channels: &'a mut DynamicAudioChannels,
+    dynamic_channel_buses: &mut DynamicChannelBuses,
+    mixer_gains: &MixerGains,
+    name: &str,
+    bus: AudioBus,
+) -> &'a DynamicAudioChannel {
+    if !dynamic_audio_channels.is_channel(name) {
+        dynamic_audio_channels
+            .create_channel(name)
+            .set_volume(gain_to_decibels(mixer_gains.gain(bus)));
+        dynamic_channel_buses.0.insert(name.to_string(), bus);
+    }
+
+    dynamic_audio_channels.channel(name)
+}
+
+/// The channel of each bus, which sounds meant for it play on.
+#[derive(SystemParam)]
+pub struct BusChannels<'w> {
+    bgm_audio_channel: Res<'w, AudioChannel<BgmChannel>>,
+    ambience_audio_channel: Res<'w, AudioChannel<AmbienceChannel>>,
+    voice_audio_channel: Res<'w, AudioChannel<VoiceChannel>>,
+    audio: Res<'w, Audio>,
+}
+
+impl BusChannels<'_> {
+    /// Plays `source` on the channel of `bus`. The master bus has no channel of its own, so it
+    /// plays on the sound effects one.
+    pub fn play(&self, bus: AudioBus, source: Handle<AudioSource>) -> PlayAudioCommand<'_> {
+        match bus {
+            AudioBus::Master | AudioBus::Sfx => self.audio.play(source),
+            AudioBus::Music => self.bgm_audio_channel.play(source),
+            AudioBus::Ambience => self.ambience_audio_channel.play(source),
+            AudioBus::Voice => self.voice_audio_channel.play(source),
+        }
+    }
+
+    pub fn state(&self, bus: AudioBus, instance: &Handle<AudioInstance>) -> PlaybackState {
+        match bus {
+            AudioBus::Master | AudioBus::Sfx => self.audio.state(instance),
+            AudioBus::Music => self.bgm_audio_channel.state(instance),
+            AudioBus::Ambience => self.ambience_audio_channel.state(instance),
+            AudioBus::Voice => self.voice_audio_channel.state(instance),
+        }
+    }
+}
+
+fn apply_mixer(
+    mixer_gains: MixerGains,
+    bus_channels: BusChannels,
+    dynamic_audio_channels: Res<DynamicAudioChannels>,
+    dynamic_channel_buses: Res<DynamicChannelBuses>,
+) {
+    let fade = AudioTween::linear(Duration::from_secs_f32(MIXER_FADE_SECS));
+
+    fade_volume(
+        bus_channels.bgm_audio_channel.as_ref(),
+        &mixer_gains,
+        AudioBus::Music,
+        &fade,
+    );
+    fade_volume(
+        bus_channels.ambience_audio_channel.as_ref(),
+        &mixer_gains,
+        AudioBus::Ambience,
+        &fade,
+    );
+    fade_volume(
+        bus_channels.voice_audio_channel.as_ref(),
+        &mixer_gains,
+        AudioBus::Voice,
+        &fade,
+    );
+    fade_volume(
+        bus_channels.audio.as_ref(),
+        &mixer_gains,
+        AudioBus::Sfx,
+        &fade,
+    );
+
+    for (name, bus) in &dynamic_channel_buses.0 {
+        if let Some(channel) = dynamic_audio_channels.get_channel(name) {
+            fade_volume(channel, &mixer_gains, *bus, &fade);
+        }
+    }
+}
+
+fn fade_volume(
+    channel: &impl AudioControl,
+    mixer_gains: &MixerGains,
+    bus: AudioBus,
+    fade: &AudioTween,
+) {
+    channel
+        .set_volume(gain_to_decibels(mixer_gains.gain(bus)))
+        .fade_in(fade.clone());
+}
+
+fn save_mixer(mixer: Res<AudioMixer>, time: Res<Time<Real>>, mut save_timer: Local<Option<Timer>>) {
+    // The first change is the mixer being loaded, which there's no need to save back.
+    if mixer.is_changed() && !mixer.is_added() {
+        *save_timer = Some(Timer::from_seconds(SAVE_DELAY_SECS, TimerMode::Once));
+    }
+
+    if let Some(timer) = save_timer.as_mut()
+        && timer.tick(time.delta()).is_finished()
+    {
+        mixer.save();
+        *save_timer = None;
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod storage {
+    use std::{fs, io::ErrorKind};
+
+    use bevy::log::warn;
+
+    /// Next to wherever the game is run from, like the rest of its files.
+    const PATH: &str = "mixer.ron";
+
+    pub fn read() -> Option<String> {
+        match fs::read_to_string(PATH) {
+            Ok(saved) => Some(saved),
+            Err(error) if error.kind() == ErrorKind::NotFound => None,
+            Err(error) => {
+                warn!("Couldn't read {PATH}: {error}");
+                None
+            }
+        }
+    }
+
+    pub fn write(serialized: &str) {
+        if let Err(error) = fs::write(PATH, serialized) {
+            warn!("Couldn't write {PATH}: {error}");
+        }
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod storage {
+    use web_sys::Storage;
+
+    const KEY: &str = "bevy-jam-7.mixer";
+
+    fn local_storage() -> Option<Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    pub fn read() -> Option<String> {
+        local_storage()?.get_item(KEY).ok().flatten()
+    }
+
+    pub fn write(serialized: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.set_item(KEY, serialized);
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::BTreeMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_kira_audio::prelude::{AudioPlugin as KiraAudioPlugin, *};
+
+use crate::assets::{
+    AssetCategory, AssetCollection, AssetCollectionAppExt, AssetCollectionLoadState, AssetManifest,
+};
+
+mod cues;
+mod mixer;
+mod music;
+mod spatial;
+mod stems;
+
+pub use cues::*;
+pub use mixer::*;
+pub use music::*;
+pub use spatial::*;
+pub use stems::*;
+
+pub struct AudioPlugin;
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            KiraAudioPlugin,
+            MixerPlugin,
+            MusicDirectorPlugin,
+            MusicStemsPlugin,
+            SoundCuesPlugin,
+            SpatialSoundPlugin,
+        ));
+
+        app.add_asset_collection::<Music>();
+        app.add_asset_collection::<SoundEffects>();
+
+        app.add_audio_channel::<BgmChannel>();
+        app.add_audio_channel::<AmbienceChannel>();
+        app.add_audio_channel::<VoiceChannel>();
+
+        app.add_observer(on_play_sound_effect);
+        app.add_observer(on_play_audio_channel);
+        app.add_observer(on_stop_audio_channel);
+    }
+}
+
+#[derive(Resource)]
+pub struct BgmChannel;
+
+#[derive(Resource)]
+pub struct AmbienceChannel;
+
+#[derive(Resource)]
+pub struct VoiceChannel;
+
+pub struct Music;
+
+impl AssetCategory for Music {
+    type Asset = AudioSource;
+
+    const NAME: &'static str = "music";
+
+    fn entries(manifest: &AssetManifest) -> &BTreeMap<String, String> {
+        &manifest.music
+    }
+}
+
+pub struct SoundEffects;
+
+impl AssetCategory for SoundEffects {
+    type Asset = AudioSource;
+
+    const NAME: &'static str = "sfx";
+
+    fn entries(manifest: &AssetManifest) -> &BTreeMap<String, String> {
+        &manifest.sfx
+    }
+}
+
+pub type MusicHandles = AssetCollection<Music>;
+
+pub type SoundEffectHandles = AssetCollection<SoundEffects>;
+
+#[derive(SystemParam)]
+pub struct AudioLoadStates<'w> {
+    pub music_load_state: Res<'w, AssetCollectionLoadState<Music>>,
+    pub sound_effects_load_state: Res<'w, AssetCollectionLoadState<SoundEffects>>,
+}
+
+pub struct PlaybackSettings {
+    pub emitter: Option<Entity>,
+    pub fade_in: Option<AudioTween>,
+    pub loop_from: Option<f64>,
+    pub loop_until: Option<f64>,
+    pub panning: f32,
+    pub playback_rate: f64,
+    pub reverse: bool,
+    pub volume: f32,
+}
+
+impl PlaybackSettings {
+    pub fn from_volume(volume: f32) -> Self {
+        Self {
+            volume,
+            ..Default::default()
+        }
+    }
+
+    pub fn looped(mut self) -> Self {
+        self.loop_from = Some(0.0);
+        self
+    }
+
+    pub fn with_emitter(mut self, emitter: Entity) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    pub fn with_playback_rate(mut self, playback_rate: f64) -> Self {
+        self.playback_rate = playback_rate;
+        self
+    }
+}
+
+impl Default for PlaybackSettings {
+    fn default() -> Self {
+        Self {
+            emitter: None,
+            fade_in: None,
+            loop_from: None,
+            loop_until: None,
+            panning: 0.5,
+            playback_rate: 1.0,
+            reverse: false,
+            volume: 1.0,
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct PlayMusic {
+    name: String,
+    settings: Option<PlaybackSettings>,
+}
+
+impl PlayMusic {
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            name,
+            settings: None,
+        }
+    }
+
+    pub fn with_settings(mut self, settings: PlaybackSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+}
+
+#[derive(Event, Default)]
+pub struct StopMusic {
+    fade_out: Option<AudioTween>,
+}
+
+impl StopMusic {
+    pub fn with_fade_out(mut self, fade_out: AudioTween) -> Self {
+        self.fade_out = Some(fade_out);
+        self
+    }
+}
+
+#[derive(Event)]
+pub struct PlaySoundEffect {
+    pub name: String,
+    pub settings: Option<PlaybackSettings>,
+}
+
+impl PlaySoundEffect {
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            name,
+            settings: None,
+        }
+    }
+
+    pub fn with_settings(mut self, settings: PlaybackSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+}
+
+#[derive(Event)]
+pub struct PlayAudioChannel {
+    channel: String,
+    name: String,
+    settings: Option<PlaybackSettings>,
+    music: bool,
+}
+
+impl PlayAudioChannel {
+    pub fn new(channel: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            channel: channel.into(),
+            name: name.into(),
+            settings: None,
+            music: false,
+        }
+    }
+
+    pub fn with_settings(mut self, settings: PlaybackSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    pub fn with_music(mut self) -> Self {
+        self.music = true;
+        self
+    }
+}
+
+#[derive(Event)]
+pub struct StopAudioChannel {
+    channel: String,
+    fade_out: Option<AudioTween>,
+}
+
+impl StopAudioChannel {
+    pub fn new(channel: impl Into<String>) -> Self {
+        Self {
+            channel: channel.into(),
+            fade_out: None,
+        }
+    }
+
+    pub fn with_fade_out(mut self, fade_out: AudioTween) -> Self {
+        self.fade_out = Some(fade_out);
+        self
+    }
+}
+
+fn on_play_sound_effect(
+    play_sfx: On<PlaySoundEffect>,
+    mut spatial_emitters: Query<&mut SpatialEmitter>,
+    sound_effect_handles: Res<SoundEffectHandles>,
+    audio: Res<Audio>,
+) {
+    let event = play_sfx.event();
+    let mut play_audio_command =
+        audio.play(sound_effect_handles.get(&event.name).unwrap_or_default());
+
+    if let Some(settings) = &event.settings {
+        play_audio_with_settings(
+            &mut play_audio_command,
+            settings,
+            settings
+                .emitter
+                .and_then(|entity| spatial_emitters.get_mut(entity).ok()),
+        );
+    }
+}
+
+fn on_play_audio_channel(
+    play_channel: On<PlayAudioChannel>,
+    mut audio: ResMut<DynamicAudioChannels>,
+    mut dynamic_channel_buses: ResMut<DynamicChannelBuses>,
+    mixer_gains: MixerGains,
+    mut spatial_emitters: Query<&mut SpatialEmitter>,
+    music_handles: Res<MusicHandles>,
+    sound_effect_handles: Res<SoundEffectHandles>,
+) {
+    let event = play_channel.event();
+    let bus = match event.music {
+        true => AudioBus::Music,
+        false => AudioBus::Sfx,
+    };
+    let channel = mixed_channel(
+        &mut audio,
+        &mut dynamic_channel_buses,
+        &mixer_gains,
+        &event.channel,
+        bus,
+    );
+    let handle = match event.music {
+        true => music_handles.get(&event.name),
+        false => sound_effect_handles.get(&event.name),
+    };
+    let mut play_audio_command = channel.play(handle.unwrap_or_default());
+
+    if let Some(settings) = &event.settings {
+        play_audio_with_settings(
+            &mut play_audio_command,
+            settings,
+            settings
+                .emitter
+                .and_then(|entity| spatial_emitters.get_mut(entity).ok()),
+        );
+    };
+}
+
+fn on_stop_audio_channel(stop_channel: On<StopAudioChannel>, audio: ResMut<DynamicAudioChannels>) {
+    let event = stop_channel.event();
+
+    if let Some(channel) = audio.get_channel(&event.channel) {
+        let mut tween_command = channel.stop();
+
+        if let Some(fade_out_tween) = &event.fade_out {
+            tween_command.fade_out(fade_out_tween.clone());
+        }
+    }
+}
+
+fn play_audio_with_settings(
+    play_audio_command: &mut PlayAudioCommand,
+    settings: &PlaybackSettings,
+    opt_spatial_emitter: Option<Mut<SpatialEmitter>>,
+) {
+    if settings.reverse {
+        play_audio_command.reverse();
+    }
+
+    if let Some(fade_in) = &settings.fade_in {
+        play_audio_command.fade_in(fade_in.clone());
+    }
+
+    if let Some(loop_from) = settings.loop_from {
+        play_audio_command.loop_from(loop_from);
+    }
+
+    if let Some(loop_until) = settings.loop_until {
+        play_audio_command.loop_until(loop_until);
+    }
+
+    play_audio_command.with_playback_rate(settings.playback_rate);
+
+    // The emitter takes over volume and panning from the next frame on, so the sound starts silent
+    // instead of at full volume wherever the emitter is.
+    match opt_spatial_emitter {
+        Some(mut spatial_emitter) => {
+            spatial_emitter.track(play_audio_command.handle(), settings.volume);
+            play_audio_command.with_volume(Decibels::SILENCE);
+        }
+        None => {
+            play_audio_command
+                .with_panning(settings.panning)
+                .with_volume(settings.volume);
+        }
+    }
+}
@@ -0,0 +1,518 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_kira_audio::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    assets::{
+        AssetCategory, AssetCollection, AssetCollectionAppExt, AssetManifest, RonAssetPlugin,
+    },
+    state::AppState,
+};
+
+use super::{
+    AudioBus, BgmChannel, DynamicChannelBuses, MixerGains, MusicHandles, MusicParameters,
+    MusicStem, PlayMusic, PlayingStem, SpatialEmitter, StopMusic, gain_to_decibels, mixed_channel,
+    play_audio_with_settings, stem_channel,
+};
+
+const SOUNDTRACK_NAME: &str = "main";
+
+pub struct MusicDirectorPlugin;
+
+impl Plugin for MusicDirectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<Soundtrack>::new(&["soundtrack.ron"]));
+        app.add_asset_collection::<Soundtracks>();
+
+        app.init_resource::<MusicDirector>();
+        app.init_resource::<MusicDirectorSettings>();
+
+        app.add_systems(OnEnter(AppState::MainMenu), play_state_playlist("menu"));
+        app.add_systems(OnEnter(AppState::Falling), play_state_playlist("falling"));
+        app.add_systems(
+            OnEnter(AppState::Exploring),
+            play_state_playlist("exploring"),
+        );
+        app.add_systems(OnEnter(AppState::Ending), play_state_playlist("ending"));
+        app.add_systems(
+            Update,
+            (switch_queued_music, advance_playlist)
+                .chain()
+                .run_if(not(in_state(AppState::Loading))),
+        );
+
+        app.add_observer(on_play_playlist);
+        app.add_observer(on_play_music);
+        app.add_observer(on_stop_music);
+    }
+}
+
+/// How one music file is laid out, so it can loop and be switched in time with the beat.
+#[derive(Deserialize, Clone, Debug)]
+pub struct MusicTrack {
+    /// Name of the file in the asset manifest's music.
+    pub music: String,
+    pub bpm: f64,
+    #[serde(default = "MusicTrack::default_beats_per_bar")]
+    pub beats_per_bar: u32,
+    /// Start of the looping section, in seconds. Everything before it plays once, as an intro.
+    /// Tracks without one play once and end.
+    #[serde(default)]
+    pub loop_from: Option<f64>,
+    /// End of the looping section, in seconds. Defaults to the end of the file.
+    #[serde(default)]
+    pub loop_until: Option<f64>,
+    /// Layers played along with the track, faded in and out with the game's [`MusicParameters`].
+    #[serde(default)]
+    pub stems: Vec<MusicStem>,
+}
+
+impl MusicTrack {
+    fn default_beats_per_bar() -> u32 {
+        4
+    }
+
+    fn bar_secs(&self) -> f64 {
+        60.0 / self.bpm * self.beats_per_bar as f64
+    }
+
+    /// Sets up the track's loop, which its stems share so they stay in sync.
+    fn configure(&self, play_audio_command: &mut PlayAudioCommand, fade_in: Option<&AudioTween>) {
+        if let Some(loop_from) = self.loop_from {
+            play_audio_command.loop_from(loop_from);
+
+            if let Some(loop_until) = self.loop_until {
+                play_audio_command.loop_until(loop_until);
+            }
+        }
+
+        if let Some(fade_in) = fade_in {
+            play_audio_command.fade_in(fade_in.clone());
+        }
+    }
+
+    /// Position, in seconds, of the first bar line after `position`.
+    fn next_bar(&self, position: f64) -> f64 {
+        let bar_secs = self.bar_secs();
+        ((position / bar_secs).floor() + 1.0) * bar_secs
+    }
+}
+
+/// Tracks played one after the other.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Playlist {
+    pub tracks: Vec<String>,
+    /// Whether to start over after the last track. A looping track never ends, so it keeps the
+    /// playlist on it regardless.
+    #[serde(default)]
+    pub repeat: bool,
+}
+
+#[derive(Asset, TypePath, Deserialize, Default)]
+pub struct Soundtrack {
+    #[serde(default)]
+    pub tracks: BTreeMap<String, MusicTrack>,
+    #[serde(default)]
+    pub playlists: BTreeMap<String, Playlist>,
+}
+
+pub struct Soundtracks;
+
+impl AssetCategory for Soundtracks {
+    type Asset = Soundtrack;
+
+    const NAME: &'static str = "soundtracks";
+
+    fn entries(manifest: &AssetManifest) -> &BTreeMap<String, String> {
+        &manifest.soundtracks
+    }
+}
+
+pub type SoundtrackHandles = AssetCollection<Soundtracks>;
+
+#[derive(Resource)]
+pub struct MusicDirectorSettings {
+    /// How the old music fades out and the new one fades in when switching.
+    pub crossfade: AudioTween,
+    /// Roughly how long, in seconds, stems take to follow a change of their parameter. Stems follow
+    /// right away when it's zero.
+    pub stem_smoothing_secs: f32,
+}
+
+impl Default for MusicDirectorSettings {
+    fn default() -> Self {
+        Self {
+            crossfade: AudioTween::new(Duration::from_secs(2), AudioEasing::InOutPowi(2)),
+            stem_smoothing_secs: 1.5,
+        }
+    }
+}
+
+/// When a requested change of music happens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MusicSwitch {
+    Now,
+    /// Once the current track reaches its next bar line, so the change lands on the beat.
+    NextBar,
+}
+
+/// Trigger this to crossfade the background music into a playlist of the soundtrack.
+#[derive(Event)]
+pub struct PlayPlaylist {
+    name: String,
+    switch: MusicSwitch,
+}
+
+impl PlayPlaylist {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            switch: MusicSwitch::Now,
+        }
+    }
+
+    pub fn at_next_bar(mut self) -> Self {
+        self.switch = MusicSwitch::NextBar;
+        self
+    }
+}
+
+/// Keeps track of what's playing on the [`BgmChannel`] and what's due to play next.
+#[derive(Resource, Default)]
+pub struct MusicDirector {
+    current: Option<CurrentMusic>,
+    queued: Option<QueuedPlaylist>,
+}
+
+impl MusicDirector {
+    pub(super) fn stems_mut(&mut self) -> impl Iterator<Item = &mut PlayingStem> {
+        self.current
+            .iter_mut()
+            .flat_map(|current| current.stems.iter_mut())
+    }
+
+    /// Name of the playlist being played, if the current music comes from one.
+    pub fn playlist(&self) -> Option<&str> {
+        self.current
+            .as_ref()
+            .and_then(|current| current.playlist.as_deref())
+    }
+}
+
+struct CurrentMusic {
+    instance: Handle<AudioInstance>,
+    stems: Vec<PlayingStem>,
+    /// `None` for music started on its own with [`PlayMusic`].
+    playlist: Option<String>,
+    track_index: usize,
+    track: Option<MusicTrack>,
+    last_position: f64,
+}
+
+impl CurrentMusic {
+    fn stop(&self, audio_instances: &mut Assets<AudioInstance>, fade_out: &AudioTween) {
+        let stem_instances = self.stems.iter().map(|stem| &stem.instance);
+
+        for handle in [&self.instance].into_iter().chain(stem_instances) {
+            if let Some(instance) = audio_instances.get_mut(handle) {
+                instance.stop(fade_out.clone());
+            }
+        }
+    }
+}
+
+struct QueuedPlaylist {
+    name: String,
+    crossfade: AudioTween,
+    /// Position of the current track at which to switch, once known.
+    switch_at: Option<f64>,
+}
+
+fn play_state_playlist(name: &'static str) -> impl Fn(Commands) {
+    move |mut commands: Commands| {
+        commands.trigger(PlayPlaylist::new(name).at_next_bar());
+    }
+}
+
+fn on_play_playlist(
+    play_playlist: On<PlayPlaylist>,
+    mut director: ResMut<MusicDirector>,
+    settings: Res<MusicDirectorSettings>,
+) {
+    if director.playlist() == Some(play_playlist.name.as_str()) {
+        director.queued = None;
+        return;
+    }
+
+    let has_bars = director
+        .current
+        .as_ref()
+        .is_some_and(|current| current.track.is_some());
+
+    director.queued = Some(QueuedPlaylist {
+        name: play_playlist.name.clone(),
+        crossfade: settings.crossfade.clone(),
+        // Without a current track there's no bar to wait for.
+        switch_at: match play_playlist.switch {
+            MusicSwitch::NextBar if has_bars => None,
+            MusicSwitch::NextBar | MusicSwitch::Now => Some(0.0),
+        },
+    });
+}
+
+fn switch_queued_music(
+    mut director: ResMut<MusicDirector>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    mut music_player: MusicPlayer,
+    soundtrack_handles: Res<SoundtrackHandles>,
+    soundtracks: Res<Assets<Soundtrack>>,
+) {
+    let director = director.as_mut();
+    let Some(queued) = &mut director.queued else {
+        return;
+    };
+
+    if let Some(current) = &mut director.current
+        && let Some(track) = &current.track
+        && let Some(position) = audio_instances
+            .get(&current.instance)
+            .and_then(|instance| instance.state().position())
+    {
+        let switch_at = match queued.switch_at {
+            Some(switch_at) => switch_at,
+            None => {
+                current.last_position = position;
+                *queued.switch_at.insert(track.next_bar(position))
+            }
+        };
+        // A looping track jumping back also means it went past the bar it was waiting for.
+        let looped = position < current.last_position;
+        current.last_position = position;
+
+        if position < switch_at && !looped {
+            return;
+        }
+    }
+
+    let Some(queued) = director.queued.take() else {
+        return;
+    };
+    let Some(soundtrack) = soundtrack_handles
+        .get(SOUNDTRACK_NAME)
+        .and_then(|handle| soundtracks.get(&handle))
+    else {
+        return;
+    };
+    let Some(playlist) = soundtrack.playlists.get(&queued.name) else {
+        debug!("No playlist \"{}\" in the soundtrack", queued.name);
+        return;
+    };
+
+    if let Some(current) = director.current.take() {
+        current.stop(&mut audio_instances, &queued.crossfade);
+    }
+
+    director.current = music_player.play_track(
+        soundtrack,
+        &queued.name,
+        playlist,
+        0,
+        Some(queued.crossfade),
+    );
+}
+
+/// Moves on to the next track of the playlist once the current one ends.
+fn advance_playlist(
+    mut director: ResMut<MusicDirector>,
+    mut music_player: MusicPlayer,
+    audio_instances: Res<Assets<AudioInstance>>,
+    soundtrack_handles: Res<SoundtrackHandles>,
+    soundtracks: Res<Assets<Soundtrack>>,
+) {
+    let Some(current) = &director.current else {
+        return;
+    };
+    let Some(playlist_name) = &current.playlist else {
+        return;
+    };
+
+    // The instance only shows up once the channel starts playing it.
+    let ended = audio_instances
+        .get(&current.instance)
+        .is_some_and(|instance| matches!(instance.state(), PlaybackState::Stopped));
+
+    if !ended {
+        return;
+    }
+
+    let Some(soundtrack) = soundtrack_handles
+        .get(SOUNDTRACK_NAME)
+        .and_then(|handle| soundtracks.get(&handle))
+    else {
+        return;
+    };
+    let Some(playlist) = soundtrack.playlists.get(playlist_name) else {
+        return;
+    };
+
+    let next_index = current.track_index + 1;
+    let next = if next_index < playlist.tracks.len() {
+        Some(next_index)
+    } else if playlist.repeat {
+        Some(0)
+    } else {
+        None
+    };
+
+    director.current = next.and_then(|index| {
+        music_player.play_track(soundtrack, playlist_name, playlist, index, None)
+    });
+}
+
+/// Starts tracks on the [`BgmChannel`], along with their stems.
+#[derive(SystemParam)]
+struct MusicPlayer<'w> {
+    music_handles: Res<'w, MusicHandles>,
+    music_parameters: Res<'w, MusicParameters>,
+    bgm_audio_channel: Res<'w, AudioChannel<BgmChannel>>,
+    dynamic_audio_channels: ResMut<'w, DynamicAudioChannels>,
+    dynamic_channel_buses: ResMut<'w, DynamicChannelBuses>,
+    mixer_gains: MixerGains<'w>,
+}
+
+impl MusicPlayer<'_> {
+    fn play_track(
+        &mut self,
+        soundtrack: &Soundtrack,
+        playlist_name: &str,
+        playlist: &Playlist,
+        track_index: usize,
+        fade_in: Option<AudioTween>,
+    ) -> Option<CurrentMusic> {
+        let track_name = playlist.tracks.get(track_index)?;
+        let Some(track) = soundtrack.tracks.get(track_name) else {
+            warn!("Playlist \"{playlist_name}\" refers to unknown track \"{track_name}\"");
+            return None;
+        };
+        let Some(music) = self.music_handles.get(&track.music) else {
+            warn!(
+                "Track \"{track_name}\" refers to unknown music \"{}\"",
+                track.music
+            );
+            return None;
+        };
+
+        let mut play_audio_command = self.bgm_audio_channel.play(music);
+        track.configure(&mut play_audio_command, fade_in.as_ref());
+        let instance = play_audio_command.handle();
+
+        // Stems start in the same frame as the track, which is what keeps them in sync with it.
+        let mut stems = Vec::with_capacity(track.stems.len());
+
+        for (index, stem) in track.stems.iter().enumerate() {
+            let Some(music) = self.music_handles.get(&stem.music) else {
+                warn!(
+                    "A stem of track \"{track_name}\" refers to unknown music \"{}\"",
+                    stem.music
+                );
+                continue;
+            };
+            let gain = stem.gain(&self.music_parameters);
+            let mut play_audio_command = mixed_channel(
+                &mut self.dynamic_audio_channels,
+                &mut self.dynamic_channel_buses,
+                &self.mixer_gains,
+                &stem_channel(index),
+                AudioBus::Music,
+            )
+            .play(music);
+            track.configure(&mut play_audio_command, fade_in.as_ref());
+            play_audio_command.with_volume(gain_to_decibels(gain));
+
+            stems.push(PlayingStem {
+                instance: play_audio_command.handle(),
+                stem: stem.clone(),
+                gain,
+            });
+        }
+
+        Some(CurrentMusic {
+            instance,
+            stems,
+            playlist: Some(playlist_name.to_string()),
+            track_index,
+            track: Some(track.clone()),
+            last_position: 0.0,
+        })
+    }
+}
+
+/// Crossfades into a single music file, outside of any playlist.
+fn on_play_music(
+    play_music: On<PlayMusic>,
+    mut director: ResMut<MusicDirector>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    mut spatial_emitters: Query<&mut SpatialEmitter>,
+    settings: Res<MusicDirectorSettings>,
+    music_handles: Res<MusicHandles>,
+    bgm_audio_channel: Res<AudioChannel<BgmChannel>>,
+) {
+    let event = play_music.event();
+
+    director.queued = None;
+
+    if let Some(current) = director.current.take() {
+        current.stop(&mut audio_instances, &settings.crossfade);
+    }
+
+    let mut play_audio_command =
+        bgm_audio_channel.play(music_handles.get(&event.name).unwrap_or_default());
+    let playback_settings = event.settings.as_ref();
+
+    match playback_settings {
+        Some(playback_settings) => play_audio_with_settings(
+            &mut play_audio_command,
+            playback_settings,
+            playback_settings
+                .emitter
+                .and_then(|entity| spatial_emitters.get_mut(entity).ok()),
+        ),
+        None => {
+            play_audio_command.fade_in(settings.crossfade.clone());
+        }
+    }
+
+    director.current = Some(CurrentMusic {
+        instance: play_audio_command.handle(),
+        stems: Vec::new(),
+        playlist: None,
+        track_index: 0,
+        track: None,
+        last_position: 0.0,
+    });
+}
+
+fn on_stop_music(
+    stop_music: On<StopMusic>,
+    mut director: ResMut<MusicDirector>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    bgm_audio_channel: Res<AudioChannel<BgmChannel>>,
+) {
+    let event = stop_music.event();
+    let mut tween_command = bgm_audio_channel.stop();
+
+    if let Some(fade_out_tween) = &event.fade_out {
+        tween_command.fade_out(fade_out_tween.clone());
+    }
+
+    if let Some(current) = director.current.take() {
+        current.stop(
+            &mut audio_instances,
+            &event.fade_out.clone().unwrap_or_default(),
+        );
+    }
+
+    director.queued = None;
+}
@@ -0,0 +1,281 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_kira_audio::{SpatialAudioReceiver, prelude::*};
+use serde::Deserialize;
+
+use super::{AudioBus, BusChannels, SoundEffectHandles, gain_to_decibels};
+
+/// Max distance of emitters that don't set their own, in pixels.
+const DEFAULT_MAX_DISTANCE: f32 = 160.0;
+const DEFAULT_PANNING_WIDTH: f32 = 0.8;
+/// How long a looping emitter sound takes to fade out once its entity is gone.
+const LOOP_FADE_OUT_SECS: f32 = 0.5;
+
+pub struct SpatialSoundPlugin;
+
+impl Plugin for SpatialSoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StoppingLoops>();
+
+        app.add_systems(
+            PostUpdate,
+            (update_spatial_sounds, stop_pending_loops).after(TransformSystems::Propagate),
+        );
+
+        #[cfg(debug_assertions)]
+        app.add_systems(Update, draw_emitter_radii);
+
+        app.add_observer(start_emitter_loop);
+        app.add_observer(stop_emitter_loop);
+    }
+}
+
+/// Makes the sounds an entity plays quieter the further it is from the camera, which carries the
+/// [`SpatialAudioReceiver`], and pans them towards its side of the screen.
+///
+/// Sounds are attached to an emitter by playing them with an emitter in their
+/// [`PlaybackSettings`](super::PlaybackSettings), or with an [`EmitterLoop`].
+#[derive(Component, Clone, Debug)]
+#[require(Transform)]
+pub struct SpatialEmitter {
+    /// Distance, in pixels, past which the emitter can't be heard.
+    pub max_distance: f32,
+    pub rolloff: Rolloff,
+    /// How far towards one speaker the emitter can pan, from 0 (always centered) to 1.
+    pub panning_width: f32,
+    instances: Vec<SpatialInstance>,
+}
+
+impl SpatialEmitter {
+    pub fn new(max_distance: f32) -> Self {
+        Self {
+            max_distance,
+            rolloff: Rolloff::default(),
+            panning_width: DEFAULT_PANNING_WIDTH,
+            instances: Vec::new(),
+        }
+    }
+
+    pub fn with_rolloff(mut self, rolloff: Rolloff) -> Self {
+        self.rolloff = rolloff;
+        self
+    }
+
+    pub fn with_panning_width(mut self, panning_width: f32) -> Self {
+        self.panning_width = panning_width;
+        self
+    }
+
+    /// Attaches a sound to the emitter. `volume` is the one it was played at, in decibels, which
+    /// the emitter lowers with distance.
+    pub(super) fn track(&mut self, instance: Handle<AudioInstance>, volume: f32) {
+        self.instances.push(SpatialInstance { instance, volume });
+    }
+}
+
+impl Default for SpatialEmitter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_DISTANCE)
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SpatialInstance {
+    instance: Handle<AudioInstance>,
+    volume: f32,
+}
+
+/// How an emitter's volume falls off with distance. Every curve reaches silence at the emitter's
+/// max distance.
+#[derive(Deserialize, Clone, Copy, Default, Debug)]
+pub enum Rolloff {
+    /// Fades evenly.
+    Linear,
+    /// Drops quickly close to the emitter, then tails off.
+    #[default]
+    Quadratic,
+    /// Full volume up to `reference_distance`, then falls with the inverse of the distance, like
+    /// sound does in the open.
+    Inverse { reference_distance: f32 },
+}
+
+impl Rolloff {
+    /// Gain, between 0 and 1, of an emitter `distance` away.
+    pub fn gain(self, distance: f32, max_distance: f32) -> f32 {
+        if distance >= max_distance {
+            return 0.0;
+        }
+
+        let remaining = 1.0 - distance / max_distance;
+
+        match self {
+            Self::Linear => remaining,
+            Self::Quadratic => remaining * remaining,
+            Self::Inverse { reference_distance } => {
+                let reference_distance = reference_distance.max(1.0);
+                // Tapered by the linear curve, since the inverse one never reaches silence.
+                reference_distance / distance.max(reference_distance) * remaining
+            }
+        }
+    }
+}
+
+/// A sound an emitter plays on a loop from when it spawns until it despawns, like a dripping tap.
+#[derive(Component, Clone, Debug)]
+#[require(SpatialEmitter)]
+pub struct EmitterLoop {
+    /// Name of the file in the asset manifest's sfx.
+    pub sfx: String,
+    pub bus: AudioBus,
+    /// In decibels.
+    pub volume: f32,
+    instance: Option<Handle<AudioInstance>>,
+}
+
+impl EmitterLoop {
+    pub fn new(sfx: impl Into<String>) -> Self {
+        Self {
+            sfx: sfx.into(),
+            bus: AudioBus::Ambience,
+            volume: 0.0,
+            instance: None,
+        }
+    }
+
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+}
+
+/// Loops whose entity went away before the audio backend started them, which are stopped as soon
+/// as it does.
+#[derive(Resource, Default)]
+struct StoppingLoops(Vec<(Handle<AudioInstance>, AudioBus)>);
+
+fn start_emitter_loop(
+    insert: On<Insert, EmitterLoop>,
+    mut emitters: Query<(&mut EmitterLoop, &mut SpatialEmitter)>,
+    sound_effect_handles: Res<SoundEffectHandles>,
+    bus_channels: BusChannels,
+) {
+    let Ok((mut emitter_loop, mut emitter)) = emitters.get_mut(insert.entity) else {
+        return;
+    };
+    let Some(sfx) = sound_effect_handles.get(&emitter_loop.sfx) else {
+        warn!(
+            "Emitter loop refers to unknown sfx \"{}\"",
+            emitter_loop.sfx
+        );
+        return;
+    };
+
+    // Silent until the emitter sets its volume, so it doesn't start at full volume far away.
+    let mut play_audio_command = bus_channels.play(emitter_loop.bus, sfx);
+    play_audio_command.looped().with_volume(Decibels::SILENCE);
+    let instance = play_audio_command.handle();
+
+    emitter.track(instance.clone(), emitter_loop.volume);
+    emitter_loop.instance = Some(instance);
+}
+
+fn stop_emitter_loop(
+    replace: On<Replace, EmitterLoop>,
+    emitter_loops: Query<&EmitterLoop>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    mut stopping_loops: ResMut<StoppingLoops>,
+) {
+    let Ok(emitter_loop) = emitter_loops.get(replace.entity) else {
+        return;
+    };
+    let Some(instance) = &emitter_loop.instance else {
+        return;
+    };
+
+    match audio_instances.get_mut(instance) {
+        Some(audio_instance) => {
+            audio_instance.stop(AudioTween::linear(Duration::from_secs_f32(
+                LOOP_FADE_OUT_SECS,
+            )));
+        }
+        None => stopping_loops.0.push((instance.clone(), emitter_loop.bus)),
+    }
+}
+
+fn stop_pending_loops(
+    mut stopping_loops: ResMut<StoppingLoops>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    bus_channels: BusChannels,
+) {
+    stopping_loops.0.retain(|(instance, bus)| {
+        if let Some(audio_instance) = audio_instances.get_mut(instance) {
+            audio_instance.stop(AudioTween::default());
+            return false;
+        }
+
+        // Keep waiting while it's queued, but not for one that will never start.
+        matches!(bus_channels.state(*bus, instance), PlaybackState::Queued)
+    });
+}
+
+fn update_spatial_sounds(
+    listeners: Query<&GlobalTransform, With<SpatialAudioReceiver>>,
+    mut emitters: Query<(&GlobalTransform, &mut SpatialEmitter)>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    let Ok(listener) = listeners.single() else {
+        return;
+    };
+
+    for (transform, mut emitter) in &mut emitters {
+        if emitter.instances.is_empty() {
+            continue;
+        }
+
+        let offset = transform.translation().xy() - listener.translation().xy();
+        let gain = emitter.rolloff.gain(offset.length(), emitter.max_distance);
+        // Panning goes from 0 for the left speaker to 1 for the right one.
+        let side = (offset.x / emitter.max_distance).clamp(-1.0, 1.0);
+        let panning = 0.5 + 0.5 * side * emitter.panning_width.clamp(0.0, 1.0);
+
+        emitter.instances.retain(|spatial_instance| {
+            // Sounds only show up once the backend starts playing them.
+            let Some(audio_instance) = audio_instances.get_mut(&spatial_instance.instance) else {
+                return true;
+            };
+
+            if matches!(audio_instance.state(), PlaybackState::Stopped) {
+                return false;
+            }
+
+            let volume = if gain > 0.0 {
+                Decibels(gain_to_decibels(gain).0 + spatial_instance.volume)
+            } else {
+                Decibels::SILENCE
+            };
+            audio_instance.set_volume(volume, AudioTween::default());
+            audio_instance.set_panning(panning, AudioTween::default());
+
+            true
+        });
+    }
+}
+
+/// Draws how far each emitter can be heard along with the physics debug gizmos, so they're shown
+/// and hidden together.
+#[cfg(debug_assertions)]
+fn draw_emitter_radii(
+    emitters: Query<(&GlobalTransform, &SpatialEmitter)>,
+    mut gizmos: Gizmos<avian2d::prelude::PhysicsGizmos>,
+) {
+    const EMITTER_RADIUS_COLOR: Color = Color::srgba(0.4, 0.8, 1.0, 0.5);
+
+    for (transform, emitter) in &emitters {
+        gizmos.circle_2d(
+            transform.translation().xy(),
+            emitter.max_distance,
+            EMITTER_RADIUS_COLOR,
+        );
+    }
+}
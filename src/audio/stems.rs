@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use serde::Deserialize;
+
+use super::{MusicDirector, MusicDirectorSettings};
+
+pub struct MusicStemsPlugin;
+
+impl Plugin for MusicStemsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MusicParameters>();
+
+        app.add_systems(Update, update_stem_volumes);
+    }
+}
+
+/// Values between 0 and 1 describing how the game is going, which music stems follow. Parameters
+/// that were never set read as 0.
+#[derive(Resource, Default, Debug)]
+pub struct MusicParameters(BTreeMap<String, f32>);
+
+impl MusicParameters {
+    pub fn get(&self, name: &str) -> f32 {
+        self.0.get(name).copied().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: f32) {
+        self.0.insert(name.into(), value.clamp(0.0, 1.0));
+    }
+}
+
+/// A layer of a music track, played in sync with it on its own channel and faded in as its
+/// parameter rises.
+#[derive(Deserialize, Clone, Debug)]
+pub struct MusicStem {
+    /// Name of the file in the asset manifest's music.
+    pub music: String,
+    /// Name of the [`MusicParameters`] value driving the stem's volume.
+    pub parameter: String,
+    /// Parameter values at which the stem starts fading in and is fully in.
+    #[serde(default = "MusicStem::default_fade_range")]
+    pub fade_range: (f32, f32),
+}
+
+impl MusicStem {
+    fn default_fade_range() -> (f32, f32) {
+        (0.0, 1.0)
+    }
+
+    /// Volume of the stem, between 0 and 1, for the current parameters.
+    pub(super) fn gain(&self, music_parameters: &MusicParameters) -> f32 {
+        let (start, end) = self.fade_range;
+        let value = music_parameters.get(&self.parameter);
+
+        if end <= start {
+            if value >= start { 1.0 } else { 0.0 }
+        } else {
+            ((value - start) / (end - start)).clamp(0.0, 1.0)
+        }
+    }
+}
+
+pub(super) struct PlayingStem {
+    pub instance: Handle<AudioInstance>,
+    pub stem: MusicStem,
+    /// Volume the stem is at, which eases towards the one its parameter asks for.
+    pub gain: f32,
+}
+
+/// Name of the dynamic channel that plays the stem at `index` of a track.
+pub(super) fn stem_channel(index: usize) -> String {
+    format!("music_stem_{index}")
+}
+
+pub(super) fn gain_to_decibels(gain: f32) -> Decibels {
+    if gain <= 0.0 {
+        Decibels::SILENCE
+    } else {
+        Decibels((20.0 * gain.log10()).max(Decibels::SILENCE.0))
+    }
+}
+
+fn update_stem_volumes(
+    mut director: ResMut<MusicDirector>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    music_parameters: Res<MusicParameters>,
+    settings: Res<MusicDirectorSettings>,
+    time: Res<Time<Real>>,
+) {
+    /// Closest a stem gets to its target before snapping to it.
+    const GAIN_EPSILON: f32 = 0.001;
+
+    // Exponential smoothing, so stems ease out of their old volume whatever the frame rate. Without
+    // any smoothing time they snap straight to their target.
+    let smoothing = if settings.stem_smoothing_secs > 0.0 {
+        1.0 - (-time.delta_secs() / settings.stem_smoothing_secs).exp()
+    } else {
+        1.0
+    };
+
+    for playing_stem in director.stems_mut() {
+        let target = playing_stem.stem.gain(&music_parameters);
+
+        if playing_stem.gain == target {
+            continue;
+        }
+
+        playing_stem.gain += (target - playing_stem.gain) * smoothing;
+
+        if (target - playing_stem.gain).abs() < GAIN_EPSILON {
+            playing_stem.gain = target;
+        }
+
+        if let Some(instance) = audio_instances.get_mut(&playing_stem.instance) {
+            instance.set_volume(gain_to_decibels(playing_stem.gain), AudioTween::default());
+        }
+    }
+}
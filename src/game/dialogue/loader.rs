@@ -0,0 +1,41 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use thiserror::Error;
+
+use super::{DialogueParseError, DialogueScript, parse_dialogue_script};
+
+#[derive(TypePath, Default)]
+pub struct DialogueScriptLoader;
+
+#[derive(Debug, Error)]
+pub enum DialogueScriptLoaderError {
+    #[error("could not read dialogue script: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("dialogue script is not valid UTF-8: {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+    #[error("could not parse dialogue script: {0}")]
+    Parse(#[from] DialogueParseError),
+}
+
+impl AssetLoader for DialogueScriptLoader {
+    type Asset = DialogueScript;
+    type Settings = ();
+    type Error = DialogueScriptLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(parse_dialogue_script(std::str::from_utf8(&bytes)?)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dialogue"]
+    }
+}
@@ -0,0 +1,9 @@
+mod loader;
+mod parser;
+mod plugin;
+mod script;
+
+pub(super) use loader::*;
+pub use parser::*;
+pub(super) use plugin::*;
+pub use script::*;
@@ -0,0 +1,565 @@
+use thiserror::Error;
+
+use crate::game::run::RunFlag;
+
+use super::{DialogueChoice, DialogueCondition, DialogueNode, DialogueScript, DialogueStep};
+
+#[derive(Debug, Error)]
+#[error("line {line}, column {column}: {kind}")]
+pub struct DialogueParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: DialogueParseErrorKind,
+}
+
+#[derive(Debug, Error)]
+pub enum DialogueParseErrorKind {
+    #[error("expected a node header, like `== name`, before any content")]
+    OutsideNode,
+    #[error("expected a node name after `==`")]
+    MissingNodeName,
+    #[error("node `{0}` is defined more than once")]
+    DuplicateNode(String),
+    #[error("unknown directive `@{0}`")]
+    UnknownDirective(String),
+    #[error("directive `@{0}` expects a value")]
+    MissingDirectiveValue(String),
+    #[error("directive `@{0}` can only be used inside a node")]
+    DirectiveOutsideNode(String),
+    #[error("directives can't be inside an `if` block")]
+    DirectiveInsideIf,
+    #[error("invalid speed `{0}`, expected a number of characters per second")]
+    InvalidSpeed(String),
+    #[error("expected a choice like `* id: text -> node`")]
+    InvalidChoice,
+    #[error("expected a node name after `->`")]
+    MissingJumpTarget,
+    #[error("expected a condition after `if`")]
+    MissingCondition,
+    #[error("unknown run flag `{0}`")]
+    UnknownFlag(String),
+    #[error("`else` without a matching `if`")]
+    UnmatchedElse,
+    #[error("`end` without a matching `if`")]
+    UnmatchedEnd,
+    #[error("`if` block is never closed with `end`")]
+    UnclosedIf,
+    #[error("jump to unknown node `{0}`")]
+    UnknownNode(String),
+}
+
+/// Parses a dialogue script. The format is line based, and leading whitespace is ignored:
+///
+/// ```text
+/// # Comments start with a hash.
+/// @portrait raven_portrait
+///
+/// == start
+/// @speaker Raven
+/// @speed 30
+/// Any other line is a locale key, or text shown as is, with {variables} filled in.
+/// if KnotUntied
+///     So you untied the knot.
+/// else
+///     The rope still holds.
+/// end
+/// if $fall_image
+///     You fell with the {fall_image} watching.
+/// end
+/// -> question
+///
+/// == question
+/// Will you cross it?
+/// * yes: Yes -> accepted
+/// * no: No
+/// ```
+///
+/// Conditions are either a [`RunFlag`] or `$variable`, to check whether a variable was set, and
+/// may be preceded by `not`. A line starting with a backslash is shown without it, for text that
+/// would otherwise read as something else.
+pub fn parse_dialogue_script(source: &str) -> Result<DialogueScript, DialogueParseError> {
+    let mut parser = Parser::default();
+
+    for (index, raw_line) in source.lines().enumerate() {
+        let indent = raw_line.chars().take_while(|c| c.is_whitespace()).count();
+        let location = Location {
+            line: index + 1,
+            column: indent + 1,
+        };
+
+        parser.parse_line(raw_line.trim(), location)?;
+    }
+
+    parser.finish()
+}
+
+#[derive(Clone, Copy)]
+struct Location {
+    line: usize,
+    column: usize,
+}
+
+impl Location {
+    /// Returns the location of the character `offset` bytes into `text`, which starts at this
+    /// location.
+    fn within(self, text: &str, offset: usize) -> Self {
+        Self {
+            column: self.column + text[..offset].chars().count(),
+            ..self
+        }
+    }
+
+    fn error(self, kind: DialogueParseErrorKind) -> DialogueParseError {
+        DialogueParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+}
+
+struct OpenBlock {
+    condition: DialogueCondition,
+    then: Vec<DialogueStep>,
+    otherwise: Option<Vec<DialogueStep>>,
+    location: Location,
+}
+
+#[derive(Default)]
+struct Parser {
+    script: DialogueScript,
+    node: Option<(String, DialogueNode)>,
+    blocks: Vec<OpenBlock>,
+    /// Every node jumped to, checked once all nodes are known.
+    targets: Vec<(String, Location)>,
+}
+
+impl Parser {
+    fn parse_line(&mut self, text: &str, location: Location) -> Result<(), DialogueParseError> {
+        if text.is_empty() || text.starts_with('#') {
+            return Ok(());
+        }
+
+        if let Some(name) = text.strip_prefix("==") {
+            return self.start_node(name.trim(), location);
+        }
+
+        if let Some(directive) = text.strip_prefix('@') {
+            return self.parse_directive(directive, location);
+        }
+
+        if let Some(target) = text.strip_prefix("->") {
+            let target = target.trim();
+
+            if target.is_empty() {
+                return Err(location.error(DialogueParseErrorKind::MissingJumpTarget));
+            }
+
+            self.targets.push((target.to_string(), location));
+            return self.push_step(DialogueStep::Jump(target.to_string()), location);
+        }
+
+        if let Some(choice) = text.strip_prefix('*') {
+            let choice = self.parse_choice(text, choice, location)?;
+            return self.push_step(DialogueStep::Choice(choice), location);
+        }
+
+        if let Some(condition) = text
+            .strip_prefix("if")
+            .filter(|condition| condition.is_empty() || condition.starts_with(char::is_whitespace))
+        {
+            let condition = condition.trim();
+            let condition_location = location.within(text, text.len() - condition.len());
+            let condition = parse_condition(condition, condition_location)?;
+
+            expect_node(&mut self.node, location)?;
+            self.blocks.push(OpenBlock {
+                condition,
+                then: Vec::new(),
+                otherwise: None,
+                location,
+            });
+            return Ok(());
+        }
+
+        if text == "else" {
+            return match self.blocks.last_mut() {
+                Some(block) if block.otherwise.is_none() => {
+                    block.otherwise = Some(Vec::new());
+                    Ok(())
+                }
+                _ => Err(location.error(DialogueParseErrorKind::UnmatchedElse)),
+            };
+        }
+
+        if text == "end" {
+            let block = self
+                .blocks
+                .pop()
+                .ok_or_else(|| location.error(DialogueParseErrorKind::UnmatchedEnd))?;
+            let step = DialogueStep::If {
+                condition: block.condition,
+                then: block.then,
+                otherwise: block.otherwise.unwrap_or_default(),
+            };
+
+            return self.push_step(step, location);
+        }
+
+        let line = text.strip_prefix('\\').unwrap_or(text);
+        self.push_step(DialogueStep::Line(line.to_string()), location)
+    }
+
+    fn start_node(&mut self, name: &str, location: Location) -> Result<(), DialogueParseError> {
+        if name.is_empty() {
+            return Err(location.error(DialogueParseErrorKind::MissingNodeName));
+        }
+
+        self.finish_node()?;
+
+        if self.script.nodes.contains_key(name) {
+            return Err(location.error(DialogueParseErrorKind::DuplicateNode(name.to_string())));
+        }
+
+        self.node = Some((name.to_string(), DialogueNode::default()));
+        Ok(())
+    }
+
+    fn finish_node(&mut self) -> Result<(), DialogueParseError> {
+        if let Some(block) = self.blocks.first() {
+            return Err(block.location.error(DialogueParseErrorKind::UnclosedIf));
+        }
+
+        if let Some((name, node)) = self.node.take() {
+            self.script.nodes.insert(name, node);
+        }
+
+        Ok(())
+    }
+
+    fn push_step(
+        &mut self,
+        step: DialogueStep,
+        location: Location,
+    ) -> Result<(), DialogueParseError> {
+        let steps = match self.blocks.last_mut() {
+            Some(OpenBlock {
+                otherwise: Some(otherwise),
+                ..
+            }) => otherwise,
+            Some(block) => &mut block.then,
+            None => &mut expect_node(&mut self.node, location)?.steps,
+        };
+
+        steps.push(step);
+        Ok(())
+    }
+
+    fn parse_directive(
+        &mut self,
+        directive: &str,
+        location: Location,
+    ) -> Result<(), DialogueParseError> {
+        let (key, value) = directive
+            .split_once(char::is_whitespace)
+            .map_or((directive, ""), |(key, value)| (key, value.trim()));
+
+        if !matches!(key, "speaker" | "portrait" | "speed") {
+            return Err(location.error(DialogueParseErrorKind::UnknownDirective(key.to_string())));
+        }
+
+        if value.is_empty() {
+            return Err(
+                location.error(DialogueParseErrorKind::MissingDirectiveValue(
+                    key.to_string(),
+                )),
+            );
+        }
+
+        if !self.blocks.is_empty() {
+            return Err(location.error(DialogueParseErrorKind::DirectiveInsideIf));
+        }
+
+        // The portrait may also be set for the whole script, before the first node.
+        if self.node.is_none() && key == "portrait" {
+            self.script.portrait = Some(value.to_string());
+            return Ok(());
+        }
+
+        let node = expect_node(&mut self.node, location).map_err(|_| {
+            location.error(DialogueParseErrorKind::DirectiveOutsideNode(
+                key.to_string(),
+            ))
+        })?;
+
+        match key {
+            "speaker" => node.speaker = Some(value.to_string()),
+            "portrait" => node.portrait = Some(value.to_string()),
+            _ => {
+                let speed = value
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|speed| *speed > 0.0)
+                    .ok_or_else(|| {
+                        location.error(DialogueParseErrorKind::InvalidSpeed(value.to_string()))
+                    })?;
+                node.chars_per_second = Some(speed);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses `choice`, the part of `text` after the leading `*`.
+    fn parse_choice(
+        &mut self,
+        text: &str,
+        choice: &str,
+        location: Location,
+    ) -> Result<DialogueChoice, DialogueParseError> {
+        let invalid_choice = || location.error(DialogueParseErrorKind::InvalidChoice);
+        let (id, rest) = choice.split_once(':').ok_or_else(invalid_choice)?;
+        let (choice_text, next) = match rest.rsplit_once("->") {
+            Some((choice_text, next)) => {
+                let next_location = location.within(text, text.len() - next.trim_start().len());
+                let next = next.trim();
+
+                if next.is_empty() {
+                    return Err(next_location.error(DialogueParseErrorKind::MissingJumpTarget));
+                }
+
+                self.targets.push((next.to_string(), next_location));
+                (choice_text, Some(next.to_string()))
+            }
+            None => (rest, None),
+        };
+        let (id, choice_text) = (id.trim(), choice_text.trim());
+
+        if id.is_empty() || id.contains(char::is_whitespace) || choice_text.is_empty() {
+            return Err(invalid_choice());
+        }
+
+        Ok(DialogueChoice {
+            id: id.to_string(),
+            text: choice_text.to_string(),
+            next,
+        })
+    }
+
+    fn finish(mut self) -> Result<DialogueScript, DialogueParseError> {
+        self.finish_node()?;
+
+        for (target, location) in &self.targets {
+            if !self.script.nodes.contains_key(target) {
+                return Err(location.error(DialogueParseErrorKind::UnknownNode(target.clone())));
+            }
+        }
+
+        Ok(self.script)
+    }
+}
+
+fn expect_node(
+    node: &mut Option<(String, DialogueNode)>,
+    location: Location,
+) -> Result<&mut DialogueNode, DialogueParseError> {
+    node.as_mut()
+        .map(|(_, node)| node)
+        .ok_or_else(|| location.error(DialogueParseErrorKind::OutsideNode))
+}
+
+/// Parses `condition`, which starts at `location`.
+fn parse_condition(
+    condition: &str,
+    location: Location,
+) -> Result<DialogueCondition, DialogueParseError> {
+    if condition.is_empty() {
+        return Err(location.error(DialogueParseErrorKind::MissingCondition));
+    }
+
+    if let Some(negated) = condition
+        .strip_prefix("not")
+        .filter(|negated| negated.starts_with(char::is_whitespace))
+    {
+        let negated = negated.trim_start();
+        let negated_location = location.within(condition, condition.len() - negated.len());
+        let negated = parse_condition(negated, negated_location)?;
+        return Ok(DialogueCondition::Not(Box::new(negated)));
+    }
+
+    if let Some(variable) = condition.strip_prefix('$') {
+        return Ok(DialogueCondition::Variable(variable.to_string()));
+    }
+
+    // Flags are written the same way they're named in code, which is also how RON spells them.
+    ron::from_str::<RunFlag>(condition)
+        .map(DialogueCondition::Flag)
+        .map_err(|_| location.error(DialogueParseErrorKind::UnknownFlag(condition.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(source: &str) -> DialogueParseError {
+        parse_dialogue_script(source).expect_err("the script should be malformed")
+    }
+
+    fn lines(steps: &[DialogueStep]) -> Vec<&str> {
+        steps
+            .iter()
+            .filter_map(|step| match step {
+                DialogueStep::Line(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_speaker_lines() {
+        let script = parse_dialogue_script(
+            "# The raven.
+            @portrait raven_portrait
+
+            == start
+            @speaker npc.raven
+            @speed 30
+            So you untied the knot.
+            \\-> Not a jump.",
+        )
+        .unwrap();
+        let node = &script.nodes["start"];
+
+        assert_eq!(script.portrait.as_deref(), Some("raven_portrait"));
+        assert_eq!(node.speaker.as_deref(), Some("npc.raven"));
+        assert_eq!(node.portrait, None);
+        assert_eq!(node.chars_per_second, Some(30.0));
+        assert_eq!(
+            lines(&node.steps),
+            ["So you untied the knot.", "-> Not a jump."]
+        );
+    }
+
+    #[test]
+    fn parses_choices_and_jumps() {
+        let script = parse_dialogue_script(
+            "== start
+            Hello.
+            -> question
+
+            == question
+            Will you cross it?
+            * yes: Yes -> accepted
+            * no: No
+
+            == accepted
+            Then go.",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            script.nodes["start"].steps.as_slice(),
+            [DialogueStep::Line(_), DialogueStep::Jump(next)] if next == "question"
+        ));
+
+        let choices = script.nodes["question"]
+            .steps
+            .iter()
+            .filter_map(|step| match step {
+                DialogueStep::Choice(choice) => Some((
+                    choice.id.as_str(),
+                    choice.text.as_str(),
+                    choice.next.as_deref(),
+                )),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            choices,
+            [("yes", "Yes", Some("accepted")), ("no", "No", None)]
+        );
+    }
+
+    #[test]
+    fn parses_variable_conditions() {
+        let script = parse_dialogue_script(
+            "== start
+            if $fall_image
+                You fell with the {fall_image} watching.
+            else
+                You fell.
+            end
+            if not KnotUntied
+                The rope still holds.
+            end",
+        )
+        .unwrap();
+        let steps = &script.nodes["start"].steps;
+
+        let [
+            DialogueStep::If {
+                condition: DialogueCondition::Variable(variable),
+                then,
+                otherwise,
+            },
+            DialogueStep::If {
+                condition: DialogueCondition::Not(negated),
+                then: knot_then,
+                otherwise: knot_otherwise,
+            },
+        ] = steps.as_slice()
+        else {
+            panic!("expected two `if` blocks, got {steps:?}");
+        };
+
+        assert_eq!(variable, "fall_image");
+        assert_eq!(lines(then), ["You fell with the {fall_image} watching."]);
+        assert_eq!(lines(otherwise), ["You fell."]);
+        assert!(matches!(
+            **negated,
+            DialogueCondition::Flag(RunFlag::KnotUntied)
+        ));
+        assert_eq!(lines(knot_then), ["The rope still holds."]);
+        assert!(knot_otherwise.is_empty());
+    }
+
+    #[test]
+    fn reports_where_malformed_scripts_go_wrong() {
+        let error = parse_error("Hello.\n== start");
+        assert!(matches!(error.kind, DialogueParseErrorKind::OutsideNode));
+        assert_eq!((error.line, error.column), (1, 1));
+
+        let error = parse_error("== start\n  if not Unknown\n  end");
+        assert!(matches!(
+            &error.kind,
+            DialogueParseErrorKind::UnknownFlag(flag) if flag == "Unknown"
+        ));
+        assert_eq!((error.line, error.column), (2, 10));
+
+        let error = parse_error("== start\n* yes: Yes ->");
+        assert!(matches!(
+            error.kind,
+            DialogueParseErrorKind::MissingJumpTarget
+        ));
+        assert_eq!((error.line, error.column), (2, 14));
+
+        let error = parse_error("== start\n* yes: Yes -> nowhere");
+        assert!(matches!(
+            &error.kind,
+            DialogueParseErrorKind::UnknownNode(node) if node == "nowhere"
+        ));
+        assert_eq!((error.line, error.column), (2, 15));
+
+        let error = parse_error("== start\n\n    if $fall_image\n    Hello.\n== next");
+        assert!(matches!(error.kind, DialogueParseErrorKind::UnclosedIf));
+        assert_eq!((error.line, error.column), (3, 5));
+
+        let error = parse_error("== start\n  @speed fast");
+        assert!(matches!(
+            &error.kind,
+            DialogueParseErrorKind::InvalidSpeed(speed) if speed == "fast"
+        ));
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
@@ -0,0 +1,511 @@
+use bevy::{
+    picking::hover::Hovered,
+    prelude::*,
+    ui::auto_directional_navigation::AutoDirectionalNavigation,
+    ui_widgets::{Activate, Button},
+};
+use bevy_enhanced_input::prelude::Start;
+
+use crate::{
+    assets::AssetCollectionAppExt,
+    game::{player::Player, run::RunState},
+    input::{
+        actions::ui::{self, UiContext},
+        disable_context, enable_context,
+    },
+    localization::Localization,
+    pause::Paused,
+    state::InGame,
+    textures::TextureHandles,
+    ui::navigation::FirstNavigableNode,
+};
+
+use super::{
+    DialogueContext, DialogueScript, DialogueScriptLoader, DialogueScripts, DialogueVariables,
+    ResolvedDialogueNode,
+};
+
+pub struct DialoguePlugin;
+
+impl Plugin for DialoguePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<DialogueScript>();
+        app.init_asset_loader::<DialogueScriptLoader>();
+        app.add_asset_collection::<DialogueScripts>();
+
+        app.init_resource::<DialogueSettings>();
+
+        app.add_systems(OnExit(InGame), clear_dialogue);
+        app.add_systems(
+            Update,
+            (
+                resolve_dialogue_node,
+                show_dialogue_speaker,
+                typewrite_dialogue,
+            )
+                .chain()
+                .run_if(resource_exists::<ActiveDialogue>),
+        );
+
+        app.add_observer(start_dialogue);
+        app.add_observer(advance_dialogue);
+        app.add_observer(select_dialogue_choice);
+    }
+}
+
+#[derive(Resource)]
+pub struct DialogueSettings {
+    /// Speed at which text is revealed, unless the node sets its own.
+    pub chars_per_second: f32,
+}
+
+impl Default for DialogueSettings {
+    fn default() -> Self {
+        Self {
+            chars_per_second: 40.0,
+        }
+    }
+}
+
+/// Trigger this to open the dialogue box at the given node of a script. Does nothing if a dialogue
+/// is already open.
+#[derive(Event)]
+pub struct StartDialogue {
+    pub script: Handle<DialogueScript>,
+    pub node: String,
+    /// Name shown above the text for nodes that don't set their own speaker.
+    pub speaker: String,
+    pub variables: DialogueVariables,
+}
+
+/// Triggered when the player picks one of the choices offered by a dialogue node.
+#[derive(Event)]
+pub struct DialogueChoiceSelected {
+    pub script: AssetId<DialogueScript>,
+    pub node: String,
+    pub choice: String,
+}
+
+#[derive(Resource)]
+struct ActiveDialogue {
+    script: Handle<DialogueScript>,
+    speaker: String,
+    variables: DialogueVariables,
+    node: String,
+    /// The current node as it reads right now, or `None` until it's been resolved.
+    resolved: Option<ResolvedDialogueNode>,
+    line: usize,
+    revealed_chars: f32,
+    choices_shown: bool,
+}
+
+impl ActiveDialogue {
+    fn go_to(&mut self, node: String) {
+        self.node = node;
+        self.resolved = None;
+        self.line = 0;
+        self.revealed_chars = 0.0;
+        self.choices_shown = false;
+    }
+
+    fn current_line(&self) -> &str {
+        self.resolved
+            .as_ref()
+            .and_then(|resolved| resolved.lines.get(self.line))
+            .map_or("", String::as_str)
+    }
+
+    fn is_line_revealed(&self) -> bool {
+        self.revealed_chars as usize >= self.current_line().chars().count()
+    }
+
+    fn is_last_line(&self) -> bool {
+        let line_count = self
+            .resolved
+            .as_ref()
+            .map_or(0, |resolved| resolved.lines.len());
+
+        self.line + 1 >= line_count
+    }
+}
+
+#[derive(Component)]
+struct DialogueRoot;
+
+#[derive(Component)]
+struct DialoguePortrait;
+
+#[derive(Component)]
+struct DialogueSpeaker;
+
+#[derive(Component)]
+struct DialogueText;
+
+#[derive(Component)]
+struct DialogueChoices;
+
+/// A choice button, holding the index of the choice within its node.
+#[derive(Component)]
+struct DialogueChoiceButton(usize);
+
+const DIALOGUE_BACKGROUND_COLOR: Color = Color::srgba(0.05, 0.04, 0.07, 0.9);
+const CHOICE_BORDER_COLOR: Color = Color::srgb(0.6, 0.55, 0.45);
+const PORTRAIT_SIZE: f32 = 64.0;
+
+fn start_dialogue(
+    start: On<StartDialogue>,
+    mut commands: Commands,
+    active_dialogue: Option<Res<ActiveDialogue>>,
+) {
+    if active_dialogue.is_some() {
+        return;
+    }
+
+    commands.insert_resource(ActiveDialogue {
+        script: start.script.clone(),
+        speaker: start.speaker.clone(),
+        variables: start.variables.clone(),
+        node: start.node.clone(),
+        resolved: None,
+        line: 0,
+        revealed_chars: 0.0,
+        choices_shown: false,
+    });
+
+    commands.spawn((
+        DialogueRoot,
+        DespawnOnExit(InGame),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::End,
+            justify_content: JustifyContent::Center,
+            padding: UiRect::all(Val::Px(24.0)),
+            ..default()
+        },
+        children![(
+            Node {
+                width: Val::Percent(80.0),
+                column_gap: Val::Px(16.0),
+                padding: UiRect::all(Val::Px(16.0)),
+                ..default()
+            },
+            BackgroundColor(DIALOGUE_BACKGROUND_COLOR),
+            children![
+                (
+                    DialoguePortrait,
+                    ImageNode::default(),
+                    Node {
+                        width: Val::Px(PORTRAIT_SIZE),
+                        height: Val::Px(PORTRAIT_SIZE),
+                        flex_shrink: 0.0,
+                        ..default()
+                    },
+                ),
+                (
+                    Node {
+                        flex_grow: 1.0,
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(8.0),
+                        ..default()
+                    },
+                    children![
+                        (
+                            DialogueSpeaker,
+                            Text::default(),
+                            TextFont::from_font_size(14.0)
+                        ),
+                        (
+                            DialogueText,
+                            Text::default(),
+                            TextFont::from_font_size(18.0)
+                        ),
+                        (
+                            DialogueChoices,
+                            Node {
+                                column_gap: Val::Px(16.0),
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                        ),
+                    ],
+                ),
+            ],
+        )],
+    ));
+
+    // Lines are advanced and choices picked like menu buttons.
+    commands.run_system_cached(disable_context::<Player>);
+    commands.run_system_cached(enable_context::<UiContext>);
+}
+
+/// Evaluates the current node when entering it, and again whenever its script is reloaded so
+/// writers can see their changes without restarting the conversation, or the locale changes.
+fn resolve_dialogue_node(
+    mut commands: Commands,
+    mut active_dialogue: ResMut<ActiveDialogue>,
+    mut script_events: MessageReader<AssetEvent<DialogueScript>>,
+    scripts: Res<Assets<DialogueScript>>,
+    run_state: Res<RunState>,
+    choice_containers: Query<&Children, With<DialogueChoices>>,
+    localization: Localization,
+) {
+    let reloaded = script_events
+        .read()
+        .any(|event| event.is_modified(&active_dialogue.script))
+        || localization.is_changed();
+
+    if active_dialogue.resolved.is_some() && !reloaded {
+        return;
+    }
+
+    let resolved = scripts.get(&active_dialogue.script).and_then(|script| {
+        script.resolve(
+            &active_dialogue.node,
+            &DialogueContext {
+                run_state: &run_state,
+                variables: &active_dialogue.variables,
+                localization: &localization,
+            },
+        )
+    });
+
+    let Some(resolved) = resolved else {
+        warn!("Dialogue node \"{}\" not found", active_dialogue.node);
+        commands.run_system_cached(end_dialogue);
+        return;
+    };
+
+    if reloaded {
+        active_dialogue.line = active_dialogue
+            .line
+            .min(resolved.lines.len().saturating_sub(1));
+        active_dialogue.choices_shown = false;
+
+        for children in &choice_containers {
+            for child in children {
+                commands.entity(*child).despawn();
+            }
+        }
+    }
+
+    active_dialogue.resolved = Some(resolved);
+}
+
+fn show_dialogue_speaker(
+    active_dialogue: Res<ActiveDialogue>,
+    texture_handles: Res<TextureHandles>,
+    portrait: Single<(&mut ImageNode, &mut Node), With<DialoguePortrait>>,
+    speaker: Single<&mut Text, With<DialogueSpeaker>>,
+    localization: Localization,
+) {
+    let Some(resolved) = &active_dialogue.resolved else {
+        return;
+    };
+
+    let (mut portrait_image, mut portrait_node) = portrait.into_inner();
+    let portrait_handle = resolved
+        .portrait
+        .as_ref()
+        .and_then(|name| texture_handles.get(name))
+        .unwrap_or_default();
+    let portrait_display = if resolved.portrait.is_some() {
+        Display::Flex
+    } else {
+        Display::None
+    };
+
+    if portrait_image.image != portrait_handle {
+        portrait_image.image = portrait_handle;
+    }
+
+    if portrait_node.display != portrait_display {
+        portrait_node.display = portrait_display;
+    }
+
+    let speaker_name = localization.get(
+        resolved
+            .speaker
+            .as_ref()
+            .unwrap_or(&active_dialogue.speaker),
+    );
+    let mut speaker = speaker.into_inner();
+
+    if speaker.0 != speaker_name {
+        speaker.0 = speaker_name.to_string();
+    }
+}
+
+fn typewrite_dialogue(
+    mut commands: Commands,
+    mut active_dialogue: ResMut<ActiveDialogue>,
+    settings: Res<DialogueSettings>,
+    text: Single<&mut Text, With<DialogueText>>,
+    choices: Single<Entity, With<DialogueChoices>>,
+    time: Res<Time>,
+) {
+    let Some(resolved) = &active_dialogue.resolved else {
+        return;
+    };
+
+    let chars_per_second = resolved
+        .chars_per_second
+        .unwrap_or(settings.chars_per_second);
+    let line_chars = active_dialogue.current_line().chars().count();
+
+    active_dialogue.revealed_chars = (active_dialogue.revealed_chars
+        + chars_per_second * time.delta_secs())
+    .min(line_chars as f32);
+
+    let revealed = active_dialogue
+        .current_line()
+        .chars()
+        .take(active_dialogue.revealed_chars as usize)
+        .collect::<String>();
+    let mut text = text.into_inner();
+
+    if text.0 != revealed {
+        text.0 = revealed;
+    }
+
+    let show_choices = active_dialogue.is_line_revealed()
+        && active_dialogue.is_last_line()
+        && !active_dialogue.choices_shown;
+    let Some(resolved) = active_dialogue
+        .resolved
+        .as_ref()
+        .filter(|resolved| show_choices && !resolved.choices.is_empty())
+    else {
+        return;
+    };
+
+    commands.entity(*choices).with_children(|parent| {
+        for (index, choice) in resolved.choices.iter().enumerate() {
+            let mut button = parent.spawn((
+                DialogueChoiceButton(index),
+                Button,
+                Hovered::default(),
+                AutoDirectionalNavigation::default(),
+                Node {
+                    padding: UiRect::axes(Val::Px(16.0), Val::Px(6.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor::all(CHOICE_BORDER_COLOR),
+                children![(
+                    Text::new(choice.text.clone()),
+                    TextFont::from_font_size(16.0)
+                )],
+            ));
+
+            if index == 0 {
+                button.insert(FirstNavigableNode);
+            }
+        }
+    });
+
+    active_dialogue.choices_shown = true;
+}
+
+/// Reveals the rest of the current line if it's still being typed, or moves on otherwise.
+fn advance_dialogue(
+    _: On<Start<ui::Select>>,
+    mut commands: Commands,
+    active_dialogue: Option<ResMut<ActiveDialogue>>,
+    paused: Option<Res<State<Paused>>>,
+) {
+    // Selecting is meant for the pause menu while it's open.
+    if paused.is_some() {
+        return;
+    }
+
+    let Some(mut active_dialogue) = active_dialogue else {
+        return;
+    };
+    let Some(resolved) = &active_dialogue.resolved else {
+        return;
+    };
+
+    // Choices are buttons, so selecting one is left to the UI navigation.
+    if !resolved.choices.is_empty() && active_dialogue.is_last_line() {
+        if !active_dialogue.is_line_revealed() {
+            active_dialogue.revealed_chars = f32::MAX;
+        }
+        return;
+    }
+
+    if !active_dialogue.is_line_revealed() {
+        active_dialogue.revealed_chars = f32::MAX;
+    } else if !active_dialogue.is_last_line() {
+        active_dialogue.line += 1;
+        active_dialogue.revealed_chars = 0.0;
+    } else {
+        match resolved.next.clone() {
+            Some(next) => active_dialogue.go_to(next),
+            None => commands.run_system_cached(end_dialogue),
+        }
+    }
+}
+
+fn select_dialogue_choice(
+    activate: On<Activate>,
+    mut commands: Commands,
+    active_dialogue: Option<ResMut<ActiveDialogue>>,
+    choice_buttons: Query<&DialogueChoiceButton>,
+    choice_containers: Query<&Children, With<DialogueChoices>>,
+) {
+    let Ok(DialogueChoiceButton(index)) = choice_buttons.get(activate.entity) else {
+        return;
+    };
+    let Some(mut active_dialogue) = active_dialogue else {
+        return;
+    };
+    let Some(choice) = active_dialogue
+        .resolved
+        .as_ref()
+        .and_then(|resolved| resolved.choices.get(*index))
+    else {
+        return;
+    };
+    let next = choice.next.clone();
+
+    commands.trigger(DialogueChoiceSelected {
+        script: active_dialogue.script.id(),
+        node: active_dialogue.node.clone(),
+        choice: choice.id.clone(),
+    });
+
+    for children in &choice_containers {
+        for child in children {
+            commands.entity(*child).despawn();
+        }
+    }
+
+    match next {
+        Some(next) => active_dialogue.go_to(next),
+        None => commands.run_system_cached(end_dialogue),
+    }
+}
+
+fn end_dialogue(
+    mut commands: Commands,
+    active_dialogue: Option<Res<ActiveDialogue>>,
+    dialogue_roots: Query<Entity, With<DialogueRoot>>,
+) {
+    if active_dialogue.is_none() {
+        return;
+    }
+
+    commands.remove_resource::<ActiveDialogue>();
+    commands.run_system_cached(disable_context::<UiContext>);
+    commands.run_system_cached(enable_context::<Player>);
+
+    for entity in &dialogue_roots {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Drops the open dialogue, if any, when the run ends. Its UI is despawned along with the run.
+fn clear_dialogue(mut commands: Commands) {
+    commands.remove_resource::<ActiveDialogue>();
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+use crate::{
+    assets::{AssetCategory, AssetCollection, AssetManifest},
+    game::run::{RunFlag, RunState},
+    localization::Localization,
+};
+
+/// A conversation made of named nodes, loaded from a `.dialogue` file. See
+/// [`parse_dialogue_script`](super::parse_dialogue_script) for the format.
+#[derive(Asset, TypePath, Default, Debug)]
+pub struct DialogueScript {
+    /// Logical name of the texture shown next to the text, unless a node sets its own.
+    pub portrait: Option<String>,
+    pub nodes: BTreeMap<String, DialogueNode>,
+}
+
+#[derive(Default, Debug)]
+pub struct DialogueNode {
+    /// Key of the name shown above the text. Falls back to the speaker the dialogue started with.
+    pub speaker: Option<String>,
+    pub portrait: Option<String>,
+    /// Overrides [`DialogueSettings::chars_per_second`](super::DialogueSettings) for this node.
+    pub chars_per_second: Option<f32>,
+    pub steps: Vec<DialogueStep>,
+}
+
+#[derive(Debug)]
+pub enum DialogueStep {
+    /// Key of the line in the locale files. Text that isn't a key is shown as is.
+    Line(String),
+    /// Offered once the last line of the node is shown.
+    Choice(DialogueChoice),
+    /// Moves on to the given node after the last line, unless there are choices to make. Nothing
+    /// after a jump is part of the node.
+    Jump(String),
+    If {
+        condition: DialogueCondition,
+        then: Vec<DialogueStep>,
+        otherwise: Vec<DialogueStep>,
+    },
+}
+
+#[derive(Debug)]
+pub struct DialogueChoice {
+    /// Identifies the choice in [`DialogueChoiceSelected`](super::DialogueChoiceSelected).
+    pub id: String,
+    /// Key of the choice's text in the locale files, like lines.
+    pub text: String,
+    /// Node to go to once picked. The dialogue ends if there is none.
+    pub next: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum DialogueCondition {
+    Flag(RunFlag),
+    /// The variable was given a value when the dialogue started.
+    Variable(String),
+    Not(Box<DialogueCondition>),
+}
+
+/// Values that text can refer to as `{name}`, set when a dialogue starts. Values are looked up in
+/// the locale files too, so they can be translated along with the text around them.
+pub type DialogueVariables = BTreeMap<String, String>;
+
+/// Everything a script's conditions and text can depend on.
+pub struct DialogueContext<'a> {
+    pub run_state: &'a RunState,
+    pub variables: &'a DialogueVariables,
+    pub localization: &'a Localization<'a>,
+}
+
+/// A node with its conditions evaluated, its text translated and variables filled in.
+pub struct ResolvedDialogueNode {
+    pub speaker: Option<String>,
+    pub portrait: Option<String>,
+    pub chars_per_second: Option<f32>,
+    pub lines: Vec<String>,
+    pub choices: Vec<DialogueChoice>,
+    pub next: Option<String>,
+}
+
+impl DialogueCondition {
+    pub fn holds(&self, context: &DialogueContext) -> bool {
+        match self {
+            Self::Flag(flag) => context.run_state.is_set(*flag),
+            Self::Variable(name) => context.variables.contains_key(name),
+            Self::Not(condition) => !condition.holds(context),
+        }
+    }
+}
+
+impl DialogueScript {
+    /// Returns the given node as it reads in the given context, or `None` if there's no such node.
+    pub fn resolve(&self, node: &str, context: &DialogueContext) -> Option<ResolvedDialogueNode> {
+        let node = self.nodes.get(node)?;
+        let mut resolved = ResolvedDialogueNode {
+            speaker: node.speaker.clone(),
+            portrait: node.portrait.clone().or_else(|| self.portrait.clone()),
+            chars_per_second: node.chars_per_second,
+            lines: Vec::new(),
+            choices: Vec::new(),
+            next: None,
+        };
+
+        resolve_steps(&node.steps, context, &mut resolved);
+        Some(resolved)
+    }
+}
+
+/// Appends the steps that apply in `context` to `resolved`. Returns `false` once a jump is
+/// reached, since nothing after it is part of the node.
+fn resolve_steps(
+    steps: &[DialogueStep],
+    context: &DialogueContext,
+    resolved: &mut ResolvedDialogueNode,
+) -> bool {
+    for step in steps {
+        match step {
+            DialogueStep::Line(text) => resolved.lines.push(interpolate(text, context)),
+            DialogueStep::Choice(choice) => resolved.choices.push(DialogueChoice {
+                id: choice.id.clone(),
+                text: interpolate(&choice.text, context),
+                next: choice.next.clone(),
+            }),
+            DialogueStep::Jump(node) => {
+                resolved.next = Some(node.clone());
+                return false;
+            }
+            DialogueStep::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let branch = if condition.holds(context) {
+                    then
+                } else {
+                    otherwise
+                };
+
+                if !resolve_steps(branch, context, resolved) {
+                    return false;
+                }
+            }
+        }
+    }
+
+    true
+}
+
+/// Translates `text` and replaces every `{name}` in it with the value of the variable. Unknown
+/// variables are left as they are so they stand out while writing.
+fn interpolate(text: &str, context: &DialogueContext) -> String {
+    let text = context.localization.get(text);
+    let mut interpolated = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('{') {
+        interpolated.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+
+        match context.variables.get(&rest[1..end]) {
+            Some(value) => interpolated.push_str(context.localization.get(value)),
+            None => interpolated.push_str(&rest[..=end]),
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    interpolated.push_str(rest);
+    interpolated
+}
+
+pub struct DialogueScripts;
+
+impl AssetCategory for DialogueScripts {
+    type Asset = DialogueScript;
+
+    const NAME: &'static str = "dialogues";
+
+    fn entries(manifest: &AssetManifest) -> &BTreeMap<String, String> {
+        &manifest.dialogues
+    }
+}
+
+pub type DialogueScriptHandles = AssetCollection<DialogueScripts>;
@@ -0,0 +1,5 @@
+mod plugin;
+mod poem;
+
+pub(super) use plugin::*;
+pub use poem::*;
@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+
+use crate::{
+    assets::{AssetCollectionAppExt, RonAssetPlugin},
+    fonts::FontHandles,
+    game::{
+        journal::RunJournal,
+        rooms::{RoomId, RoomRecord},
+        run::RunState,
+    },
+    localization::LocalizedText,
+    state::AppState,
+};
+
+use super::{PoemContext, PoemHandles, PoemTemplate, Poems};
+
+const POEM_NAME: &str = "ending";
+const POEM_FONT_NAME: &str = "poem";
+/// Seconds between two lines of the poem showing up.
+const LINE_REVEAL_SECS: f32 = 1.5;
+
+pub struct EndingPlugin;
+
+impl Plugin for EndingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<PoemTemplate>::new(&["poem.ron"]));
+        app.add_asset_collection::<Poems>();
+
+        app.add_systems(OnEnter(AppState::Ending), spawn_poem);
+        app.add_systems(OnExit(AppState::Ending), stop_poem_reveal);
+        app.add_systems(
+            Update,
+            (
+                end_run_when_rooms_are_sealed
+                    .run_if(in_state(AppState::Exploring).and(resource_changed::<RoomRecord>)),
+                reveal_poem_lines
+                    .run_if(in_state(AppState::Ending).and(resource_exists::<PoemReveal>)),
+            ),
+        );
+    }
+}
+
+#[derive(Resource)]
+struct PoemReveal {
+    timer: Timer,
+    next_line: usize,
+}
+
+/// One line of the poem, hidden until its turn comes.
+#[derive(Component)]
+struct PoemLineText(usize);
+
+fn end_run_when_rooms_are_sealed(
+    mut next_state: ResMut<NextState<AppState>>,
+    room_record: Res<RoomRecord>,
+) {
+    if RoomId::all().all(|room| room_record.is_sealed(room)) {
+        next_state.set(AppState::Ending);
+    }
+}
+
+fn spawn_poem(
+    mut commands: Commands,
+    poem_handles: Res<PoemHandles>,
+    poems: Res<Assets<PoemTemplate>>,
+    font_handles: Res<FontHandles>,
+    run_state: Res<RunState>,
+    run_journal: Res<RunJournal>,
+) {
+    let Some(poem) = poem_handles
+        .get(POEM_NAME)
+        .and_then(|handle| poems.get(&handle))
+    else {
+        return;
+    };
+
+    let lines = poem.compose(&PoemContext {
+        run_state: &run_state,
+        run_journal: &run_journal,
+    });
+    let font = font_handles.get(POEM_FONT_NAME).unwrap_or_default();
+
+    commands
+        .spawn((
+            DespawnOnExit(AppState::Ending),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::BLACK),
+        ))
+        .with_children(|parent| {
+            for (index, line) in lines.into_iter().enumerate() {
+                parent.spawn((
+                    PoemLineText(index),
+                    LocalizedText::new(line),
+                    TextFont::from_font_size(20.0).with_font(font.clone()),
+                    // Keeps the height of empty lines between stanzas.
+                    Node {
+                        min_height: Val::Px(20.0),
+                        ..default()
+                    },
+                    Visibility::Hidden,
+                ));
+            }
+        });
+
+    commands.insert_resource(PoemReveal {
+        timer: Timer::from_seconds(LINE_REVEAL_SECS, TimerMode::Repeating),
+        next_line: 0,
+    });
+}
+
+fn reveal_poem_lines(
+    mut poem_reveal: ResMut<PoemReveal>,
+    mut lines: Query<(&PoemLineText, &mut Visibility)>,
+    time: Res<Time>,
+) {
+    poem_reveal.timer.tick(time.delta());
+
+    for _ in 0..poem_reveal.timer.times_finished_this_tick() {
+        for (PoemLineText(index), mut visibility) in &mut lines {
+            if *index == poem_reveal.next_line {
+                *visibility = Visibility::Inherited;
+            }
+        }
+
+        poem_reveal.next_line += 1;
+    }
+}
+
+fn stop_poem_reveal(mut commands: Commands) {
+    commands.remove_resource::<PoemReveal>();
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    assets::{AssetCategory, AssetCollection, AssetManifest},
+    game::{
+        journal::RunJournal,
+        rooms::RoomId,
+        run::{RunFlag, RunState},
+    },
+};
+
+/// Lines the ending poem is assembled from, as keys in the locale files. Each stanza and each line
+/// may only show up under a given [`PoemCondition`].
+#[derive(Asset, TypePath, Deserialize)]
+pub struct PoemTemplate {
+    /// Poem shown as-is, regardless of any other choice, when the knot was never untied.
+    pub knotted: Vec<String>,
+    pub stanzas: Vec<PoemStanza>,
+}
+
+#[derive(Deserialize)]
+pub struct PoemStanza {
+    #[serde(default)]
+    pub when: Option<PoemCondition>,
+    pub lines: Vec<PoemLine>,
+}
+
+#[derive(Deserialize)]
+pub struct PoemLine {
+    pub text: String,
+    #[serde(default)]
+    pub when: Option<PoemCondition>,
+}
+
+#[derive(Deserialize, Debug)]
+pub enum PoemCondition {
+    /// The pick made at the given prompt of the fall, `false` being the left image and `true` the
+    /// right one.
+    FallPick(usize, bool),
+    Flag(RunFlag),
+    /// The player interacted with the named object, in any room.
+    Interacted(String),
+    /// The player interacted with the named object in the given room.
+    InteractedInRoom(u8, String),
+    /// The player interacted with at least one object with the given tag.
+    InteractedWithTag(String),
+    /// At least this many rooms were left without interacting with anything.
+    RoomsWithoutInteractions(usize),
+    All(Vec<PoemCondition>),
+    Any(Vec<PoemCondition>),
+    Not(Box<PoemCondition>),
+}
+
+/// Everything about a run that a poem can depend on.
+pub struct PoemContext<'a> {
+    pub run_state: &'a RunState,
+    pub run_journal: &'a RunJournal,
+}
+
+impl PoemCondition {
+    pub fn holds(&self, context: &PoemContext) -> bool {
+        match self {
+            Self::FallPick(prompt, pick) => context.run_journal.fall_pick(*prompt) == Some(*pick),
+            Self::Flag(flag) => context.run_state.is_set(*flag),
+            Self::Interacted(object) => context.run_journal.interacted_with(object, None),
+            Self::InteractedInRoom(room, object) => context
+                .run_journal
+                .interacted_with(object, Some(RoomId(*room))),
+            Self::InteractedWithTag(tag) => context.run_journal.interacted_with_tag(tag),
+            Self::RoomsWithoutInteractions(count) => {
+                context.run_journal.rooms_without_interactions() >= *count
+            }
+            Self::All(conditions) => conditions.iter().all(|condition| condition.holds(context)),
+            Self::Any(conditions) => conditions.iter().any(|condition| condition.holds(context)),
+            Self::Not(condition) => !condition.holds(context),
+        }
+    }
+}
+
+impl PoemTemplate {
+    /// Returns the keys of the poem's lines for the given run, with an empty line between stanzas.
+    pub fn compose(&self, context: &PoemContext) -> Vec<String> {
+        if !context.run_state.is_set(RunFlag::KnotUntied) {
+            return self.knotted.clone();
+        }
+
+        let holds = |when: &Option<PoemCondition>| {
+            when.as_ref()
+                .is_none_or(|condition| condition.holds(context))
+        };
+
+        self.stanzas
+            .iter()
+            .filter(|stanza| holds(&stanza.when))
+            .map(|stanza| {
+                stanza
+                    .lines
+                    .iter()
+                    .filter(|line| holds(&line.when))
+                    .map(|line| line.text.clone())
+                    .collect::<Vec<String>>()
+            })
+            .filter(|lines| !lines.is_empty())
+            .collect::<Vec<Vec<String>>>()
+            .join(&String::new())
+    }
+}
+
+pub struct Poems;
+
+impl AssetCategory for Poems {
+    type Asset = PoemTemplate;
+
+    const NAME: &'static str = "poems";
+
+    fn entries(manifest: &AssetManifest) -> &BTreeMap<String, String> {
+        &manifest.poems
+    }
+}
+
+pub type PoemHandles = AssetCollection<Poems>;
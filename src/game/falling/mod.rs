@@ -0,0 +1,5 @@
+mod plugin;
+mod sequence;
+
+pub(super) use plugin::*;
+pub use sequence::*;
@@ -0,0 +1,234 @@
+use bevy::{
+    picking::hover::Hovered,
+    prelude::*,
+    ui::auto_directional_navigation::AutoDirectionalNavigation,
+    ui_widgets::{Activate, Button},
+};
+
+use crate::{
+    assets::{AssetCollectionAppExt, RonAssetPlugin},
+    game::{
+        journal::{RecordChoice, RunChoice},
+        seed::RunSeed,
+    },
+    input::{actions::ui::UiContext, disable_context, enable_context},
+    state::AppState,
+    textures::TextureHandles,
+    ui::navigation::FirstNavigableNode,
+};
+
+use super::{FallingPrompt, FallingSequence, FallingSequenceHandles, FallingSequences};
+
+const FALLING_SEQUENCE_NAME: &str = "intro";
+
+pub struct FallingPlugin;
+
+impl Plugin for FallingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<FallingSequence>::new(&["fall.ron"]));
+        app.add_asset_collection::<FallingSequences>();
+
+        app.add_systems(
+            OnEnter(AppState::Falling),
+            (start_falling, spawn_void_streaks),
+        );
+        app.add_systems(OnExit(AppState::Falling), stop_falling);
+        app.add_systems(
+            Update,
+            (scroll_void_streaks, advance_falling_sequence).run_if(in_state(AppState::Falling)),
+        );
+
+        app.add_observer(on_activate_fall_choice);
+    }
+}
+
+#[derive(Resource)]
+struct FallingProgress {
+    sequence: Handle<FallingSequence>,
+    next_prompt: usize,
+    timer: Timer,
+    awaiting_pick: bool,
+}
+
+#[derive(Component)]
+struct VoidStreak;
+
+#[derive(Component)]
+struct FallingPromptRoot;
+
+/// One of the two images of a falling prompt. `false` is the left image and `true` the right one.
+#[derive(Component)]
+struct FallChoice(bool);
+
+fn start_falling(
+    mut commands: Commands,
+    sequence_handles: Res<FallingSequenceHandles>,
+    sequences: Res<Assets<FallingSequence>>,
+) {
+    let sequence = sequence_handles
+        .get(FALLING_SEQUENCE_NAME)
+        .unwrap_or_default();
+    let delay = sequences
+        .get(&sequence)
+        .map(|sequence| sequence.delay_before(0))
+        .unwrap_or_default();
+
+    commands.insert_resource(FallingProgress {
+        sequence,
+        next_prompt: 0,
+        timer: Timer::from_seconds(delay, TimerMode::Once),
+        awaiting_pick: false,
+    });
+}
+
+fn stop_falling(mut commands: Commands) {
+    commands.remove_resource::<FallingProgress>();
+}
+
+const VOID_STREAK_COUNT: usize = 24;
+const VOID_STREAK_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.4);
+const VOID_HALF_EXTENTS: Vec2 = Vec2::new(160.0, 100.0);
+
+fn spawn_void_streaks(mut commands: Commands) {
+    for i in 0..VOID_STREAK_COUNT {
+        // Spread the streaks horizontally with the golden ratio so they never line up.
+        let offset = (i as f32 * 0.618_034).fract();
+        let x = (offset * 2.0 - 1.0) * VOID_HALF_EXTENTS.x;
+        let y = (i as f32 / VOID_STREAK_COUNT as f32 * 2.0 - 1.0) * VOID_HALF_EXTENTS.y;
+
+        commands.spawn((
+            VoidStreak,
+            DespawnOnExit(AppState::Falling),
+            Sprite::from_color(VOID_STREAK_COLOR, Vec2::new(1.0, 6.0 + 10.0 * offset)),
+            Transform::from_xyz(x, y, -1.0),
+        ));
+    }
+}
+
+fn scroll_void_streaks(
+    mut streaks: Query<&mut Transform, With<VoidStreak>>,
+    progress: Res<FallingProgress>,
+    sequences: Res<Assets<FallingSequence>>,
+    time: Res<Time>,
+) {
+    let Some(sequence) = sequences.get(&progress.sequence) else {
+        return;
+    };
+
+    for mut transform in &mut streaks {
+        transform.translation.y += sequence.fall_speed * time.delta_secs();
+
+        if transform.translation.y > VOID_HALF_EXTENTS.y {
+            transform.translation.y -= VOID_HALF_EXTENTS.y * 2.0;
+        }
+    }
+}
+
+fn advance_falling_sequence(
+    mut commands: Commands,
+    mut progress: ResMut<FallingProgress>,
+    mut next_state: ResMut<NextState<AppState>>,
+    sequences: Res<Assets<FallingSequence>>,
+    texture_handles: Res<TextureHandles>,
+    time: Res<Time>,
+) {
+    let Some(sequence) = sequences.get(&progress.sequence) else {
+        // Without a sequence to play there's nothing to pick, so land right away.
+        next_state.set(AppState::Exploring);
+        return;
+    };
+
+    if progress.awaiting_pick || !progress.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    match sequence.prompts.get(progress.next_prompt) {
+        Some(prompt) => {
+            spawn_falling_prompt(&mut commands, prompt, &texture_handles);
+            commands.run_system_cached(enable_context::<UiContext>);
+            progress.awaiting_pick = true;
+        }
+        None => next_state.set(AppState::Exploring),
+    }
+}
+
+fn spawn_falling_prompt(
+    commands: &mut Commands,
+    prompt: &FallingPrompt,
+    texture_handles: &TextureHandles,
+) {
+    commands.spawn((
+        FallingPromptRoot,
+        DespawnOnExit(AppState::Falling),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(48.0),
+            ..default()
+        },
+        children![
+            (
+                fall_choice_button(false, texture_handles.get(&prompt.left).unwrap_or_default()),
+                FirstNavigableNode,
+            ),
+            fall_choice_button(true, texture_handles.get(&prompt.right).unwrap_or_default()),
+        ],
+    ));
+}
+
+fn fall_choice_button(choice: bool, image: Handle<Image>) -> impl Bundle {
+    (
+        FallChoice(choice),
+        Button,
+        Hovered::default(),
+        AutoDirectionalNavigation::default(),
+        Node {
+            width: Val::Px(128.0),
+            height: Val::Px(128.0),
+            border: UiRect::all(Val::Px(2.0)),
+            ..default()
+        },
+        BorderColor::DEFAULT,
+        ImageNode::new(image),
+    )
+}
+
+fn on_activate_fall_choice(
+    activate: On<Activate>,
+    mut commands: Commands,
+    mut run_seed: ResMut<RunSeed>,
+    progress: Option<ResMut<FallingProgress>>,
+    choices: Query<&FallChoice>,
+    prompt_roots: Query<Entity, With<FallingPromptRoot>>,
+    sequences: Res<Assets<FallingSequence>>,
+) {
+    let Ok(choice) = choices.get(activate.entity) else {
+        return;
+    };
+    let Some(mut progress) = progress.filter(|progress| progress.awaiting_pick) else {
+        return;
+    };
+
+    run_seed.push_choice(choice.0);
+    commands.trigger(RecordChoice(RunChoice::FallPick {
+        prompt: progress.next_prompt,
+        pick: choice.0,
+    }));
+
+    progress.awaiting_pick = false;
+    progress.next_prompt += 1;
+
+    let delay = sequences
+        .get(&progress.sequence)
+        .map(|sequence| sequence.delay_before(progress.next_prompt))
+        .unwrap_or_default();
+    progress.timer = Timer::from_seconds(delay, TimerMode::Once);
+
+    for entity in &prompt_roots {
+        commands.entity(entity).despawn();
+    }
+
+    commands.run_system_cached(disable_context::<UiContext>);
+}
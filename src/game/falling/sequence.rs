@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::assets::{AssetCategory, AssetCollection, AssetManifest};
+
+/// Describes the opening fall: how fast the void scrolls by and which pairs of images the player
+/// picks between, in order.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct FallingSequence {
+    /// Speed, in pixels per second, at which the void scrolls past the player.
+    pub fall_speed: f32,
+    pub prompts: Vec<FallingPrompt>,
+    /// Seconds to keep falling after the last pick before landing.
+    pub landing_delay_secs: f32,
+}
+
+impl FallingSequence {
+    /// Returns the seconds to wait before showing the given prompt, or before landing if there are
+    /// no prompts left.
+    pub fn delay_before(&self, prompt: usize) -> f32 {
+        self.prompts
+            .get(prompt)
+            .map_or(self.landing_delay_secs, |prompt| prompt.delay_secs)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct FallingPrompt {
+    /// Seconds to wait, after the previous pick, before showing this prompt.
+    pub delay_secs: f32,
+    /// Texture names, as listed in the asset manifest, of the two images to pick between.
+    pub left: String,
+    pub right: String,
+}
+
+pub struct FallingSequences;
+
+impl AssetCategory for FallingSequences {
+    type Asset = FallingSequence;
+
+    const NAME: &'static str = "sequences";
+
+    fn entries(manifest: &AssetManifest) -> &BTreeMap<String, String> {
+        &manifest.sequences
+    }
+}
+
+pub type FallingSequenceHandles = AssetCollection<FallingSequences>;
@@ -0,0 +1,181 @@
+use avian2d::prelude::{Collider, CollidingEntities, CollisionLayers, Sensor};
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::Start;
+use serde::Deserialize;
+
+use crate::{
+    game::player::{Player, actions::Interact},
+    localization::{Locale, Localization},
+    physics::CollisionLayer,
+    state::AppState,
+};
+
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FocusedInteractable>();
+
+        app.add_systems(OnEnter(AppState::Exploring), spawn_interaction_prompt);
+        app.add_systems(OnExit(AppState::Exploring), clear_focused_interactable);
+        app.add_systems(
+            Update,
+            (
+                update_focused_interactable,
+                update_interaction_prompt
+                    .run_if(resource_changed::<FocusedInteractable>.or(resource_changed::<Locale>)),
+            )
+                .chain()
+                .run_if(in_state(AppState::Exploring)),
+        );
+
+        app.add_observer(on_add_interactable);
+        app.add_observer(on_remove_interactable);
+        app.add_observer(interact);
+    }
+}
+
+/// Something the player can interact with when close enough. The sensor detecting the player is
+/// spawned as a child of this entity, so interactables can be loaded from data files.
+#[derive(Component, Deserialize, Clone, Debug)]
+pub struct Interactable {
+    /// Key of the localized text shown on screen while the player is in range.
+    pub prompt: String,
+    /// Distance, in pixels, at which the player can interact with this entity.
+    pub radius: f32,
+}
+
+/// Triggered on an [`Interactable`] when the player interacts with it.
+#[derive(EntityEvent)]
+pub struct Interacted {
+    #[event_target]
+    pub target: Entity,
+    pub player: Entity,
+}
+
+/// The closest [`Interactable`] in range of the player, if any.
+#[derive(Resource, Default, Deref, PartialEq)]
+pub struct FocusedInteractable(Option<Entity>);
+
+#[derive(Component)]
+struct InteractionSensor;
+
+#[derive(Component)]
+struct InteractionPrompt;
+
+fn on_add_interactable(
+    add: On<Add, Interactable>,
+    mut commands: Commands,
+    interactables: Query<&Interactable>,
+) {
+    if let Ok(interactable) = interactables.get(add.entity) {
+        commands.spawn((
+            InteractionSensor,
+            ChildOf(add.entity),
+            Transform::default(),
+            Sensor,
+            Collider::circle(interactable.radius),
+            CollisionLayers::new(CollisionLayer::Interactable, CollisionLayer::Player),
+            CollidingEntities::default(),
+        ));
+    }
+}
+
+fn on_remove_interactable(
+    remove: On<Remove, Interactable>,
+    mut commands: Commands,
+    sensors: Query<(Entity, &ChildOf), With<InteractionSensor>>,
+) {
+    for (sensor, child_of) in &sensors {
+        if child_of.parent() == remove.entity {
+            commands.entity(sensor).despawn();
+        }
+    }
+}
+
+fn update_focused_interactable(
+    mut focused_interactable: ResMut<FocusedInteractable>,
+    sensors: Query<(&ChildOf, &CollidingEntities), With<InteractionSensor>>,
+    interactables: Query<&GlobalTransform, With<Interactable>>,
+    player: Single<(Entity, &GlobalTransform), With<Player>>,
+) {
+    let (player, player_transform) = player.into_inner();
+    let player_position = player_transform.translation().xy();
+
+    let closest = sensors
+        .iter()
+        .filter(|(_, colliding_entities)| colliding_entities.contains(&player))
+        .filter_map(|(child_of, _)| {
+            interactables
+                .get(child_of.parent())
+                .ok()
+                .map(|transform| (child_of.parent(), transform.translation().xy()))
+        })
+        .min_by(|(_, a), (_, b)| {
+            a.distance_squared(player_position)
+                .total_cmp(&b.distance_squared(player_position))
+        })
+        .map(|(entity, _)| entity);
+
+    focused_interactable.set_if_neq(FocusedInteractable(closest));
+}
+
+fn clear_focused_interactable(mut focused_interactable: ResMut<FocusedInteractable>) {
+    focused_interactable.set_if_neq(FocusedInteractable(None));
+}
+
+fn spawn_interaction_prompt(mut commands: Commands) {
+    commands.spawn((
+        DespawnOnExit(AppState::Exploring),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::End,
+            justify_content: JustifyContent::Center,
+            padding: UiRect::bottom(Val::Px(32.0)),
+            ..default()
+        },
+        children![(
+            InteractionPrompt,
+            Text::default(),
+            TextFont::from_font_size(18.0),
+            Visibility::Hidden,
+        )],
+    ));
+}
+
+fn update_interaction_prompt(
+    prompt: Single<(&mut Text, &mut Visibility), With<InteractionPrompt>>,
+    focused_interactable: Res<FocusedInteractable>,
+    interactables: Query<&Interactable>,
+    localization: Localization,
+) {
+    let (mut text, mut visibility) = prompt.into_inner();
+
+    match focused_interactable.and_then(|entity| interactables.get(entity).ok()) {
+        Some(interactable) => {
+            text.0 = format!(
+                "{} {}",
+                localization.get("prompt.interact_key"),
+                localization.get(&interactable.prompt)
+            );
+            *visibility = Visibility::Inherited;
+        }
+        None => {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+fn interact(
+    start: On<Start<Interact>>,
+    mut commands: Commands,
+    focused_interactable: Res<FocusedInteractable>,
+) {
+    if let Some(target) = **focused_interactable {
+        commands.trigger(Interacted {
+            target,
+            player: start.context,
+        });
+    }
+}
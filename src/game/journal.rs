@@ -0,0 +1,157 @@
+use std::{collections::BTreeSet, time::Duration};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{game::rooms::RoomId, state::AppState};
+
+pub struct RunJournalPlugin;
+
+impl Plugin for RunJournalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunJournal>();
+
+        app.add_systems(OnEnter(AppState::Falling), reset_run_journal);
+
+        app.add_observer(record_choice);
+        app.add_observer(log_journal_entry);
+    }
+}
+
+/// Every meaningful choice made during the current run, in the order they were made.
+#[derive(Resource, Default, Serialize, Deserialize, Debug)]
+pub struct RunJournal {
+    /// Virtual time at which the run started.
+    started_at: Duration,
+    entries: Vec<JournalEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JournalEntry {
+    /// Virtual time since the run started, so time spent paused isn't counted.
+    pub at: Duration,
+    pub choice: RunChoice,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum RunChoice {
+    /// The pick made at the given prompt of the fall, `false` being the left image and `true` the
+    /// right one.
+    FallPick {
+        prompt: usize,
+        pick: bool,
+    },
+    Knot {
+        untied: bool,
+    },
+    RoomEntered(RoomId),
+    ObjectInteracted {
+        room: RoomId,
+        object: String,
+        tags: Vec<String>,
+    },
+    /// The answer given to the raven when asked about crossing the golden bridge.
+    RavenAnswered {
+        yes: bool,
+    },
+    GoldenBridgeCrossed,
+}
+
+/// Trigger this to add a choice to the [`RunJournal`].
+#[derive(Event)]
+pub struct RecordChoice(pub RunChoice);
+
+/// Triggered after an entry is added to the [`RunJournal`].
+#[derive(Event)]
+pub struct JournalEntryAdded(pub JournalEntry);
+
+impl RunJournal {
+    pub fn choices(&self) -> impl Iterator<Item = &RunChoice> {
+        self.entries.iter().map(|entry| &entry.choice)
+    }
+
+    pub fn fall_pick(&self, prompt: usize) -> Option<bool> {
+        self.choices().find_map(|choice| match choice {
+            RunChoice::FallPick {
+                prompt: picked_prompt,
+                pick,
+            } if *picked_prompt == prompt => Some(*pick),
+            _ => None,
+        })
+    }
+
+    /// Returns whether the player interacted with the named object in `room`, or in any room if
+    /// `room` is `None`.
+    pub fn interacted_with(&self, object: &str, room: Option<RoomId>) -> bool {
+        self.choices().any(|choice| {
+            matches!(
+                choice,
+                RunChoice::ObjectInteracted {
+                    room: interacted_room,
+                    object: interacted_object,
+                    ..
+                } if interacted_object == object && room.is_none_or(|room| room == *interacted_room)
+            )
+        })
+    }
+
+    pub fn interacted_with_tag(&self, tag: &str) -> bool {
+        self.choices().any(|choice| {
+            matches!(
+                choice,
+                RunChoice::ObjectInteracted { tags, .. } if tags.iter().any(|interacted_tag| interacted_tag == tag)
+            )
+        })
+    }
+
+    /// Returns how many of the rooms the player went into they left without interacting with
+    /// anything.
+    pub fn rooms_without_interactions(&self) -> usize {
+        let mut entered = BTreeSet::new();
+        let mut interacted = BTreeSet::new();
+
+        for choice in self.choices() {
+            match choice {
+                RunChoice::RoomEntered(room) => {
+                    entered.insert(*room);
+                }
+                RunChoice::ObjectInteracted { room, .. } => {
+                    interacted.insert(*room);
+                }
+                _ => {}
+            }
+        }
+
+        entered.difference(&interacted).count()
+    }
+}
+
+fn reset_run_journal(mut run_journal: ResMut<RunJournal>, time: Res<Time<Virtual>>) {
+    *run_journal = RunJournal {
+        started_at: time.elapsed(),
+        entries: Vec::new(),
+    };
+}
+
+fn record_choice(
+    record: On<RecordChoice>,
+    mut commands: Commands,
+    mut run_journal: ResMut<RunJournal>,
+    time: Res<Time<Virtual>>,
+) {
+    let entry = JournalEntry {
+        at: time.elapsed().saturating_sub(run_journal.started_at),
+        choice: record.0.clone(),
+    };
+
+    run_journal.entries.push(entry.clone());
+    commands.trigger(JournalEntryAdded(entry));
+}
+
+fn log_journal_entry(added: On<JournalEntryAdded>) {
+    debug!(
+        "Journal entry at {:.1}s: {:?}",
+        added.0.at.as_secs_f32(),
+        added.0.choice
+    );
+}
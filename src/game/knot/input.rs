@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+use crate::input::actions::ui;
+
+pub struct KnotInputPlugin;
+
+impl Plugin for KnotInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_input_context::<KnotContext>();
+
+        app.add_systems(Startup, bind_knot_actions);
+    }
+}
+
+/// Input context active while the knot puzzle is open. It takes priority over the [`ui::UiContext`]
+/// so the keys it shares with menus only pull the rope.
+#[derive(Component, Default)]
+pub struct KnotContext;
+
+fn bind_knot_actions(mut commands: Commands) {
+    commands.spawn((
+        KnotContext,
+        ContextPriority::<KnotContext>::new(1),
+        ContextActivity::<KnotContext>::INACTIVE,
+        Actions::<KnotContext>::spawn((
+            Spawn((
+                Action::<ui::Back>::new(),
+                ActionSettings {
+                    require_reset: true,
+                    ..default()
+                },
+                bindings![GamepadButton::East, KeyCode::Escape],
+            )),
+            Spawn((
+                Action::<ui::Navigate>::new(),
+                Bindings::spawn((
+                    Cardinal::new(
+                        GamepadButton::DPadUp,
+                        GamepadButton::DPadLeft,
+                        GamepadButton::DPadDown,
+                        GamepadButton::DPadRight,
+                    ),
+                    Cardinal::new(KeyCode::KeyW, KeyCode::KeyA, KeyCode::KeyS, KeyCode::KeyD),
+                    Cardinal::new(
+                        KeyCode::ArrowUp,
+                        KeyCode::ArrowLeft,
+                        KeyCode::ArrowDown,
+                        KeyCode::ArrowRight,
+                    ),
+                )),
+            )),
+        )),
+    ));
+}
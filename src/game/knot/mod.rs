@@ -0,0 +1,7 @@
+mod input;
+mod plugin;
+mod puzzle;
+
+pub(super) use input::*;
+pub(super) use plugin::*;
+pub use puzzle::*;
@@ -0,0 +1,294 @@
+use avian2d::prelude::{Collider, RigidBody};
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::Start;
+
+use crate::{
+    game::{
+        interaction::{Interactable, Interacted},
+        journal::{RecordChoice, RunChoice},
+        player::Player,
+        run::{RunFlag, RunState},
+        seed::RunSeed,
+        slowdown::{SlowdownFalloff, SlowdownField},
+    },
+    input::{Cursor, actions::ui, disable_context, enable_context},
+    localization::LocalizedText,
+    pause::Paused,
+    state::{AppState, InGame},
+};
+
+use super::{KnotContext, KnotPull, KnotPullResult, KnotPuzzle, KnotPuzzleOutcome};
+
+/// Number of pulls needed to untie the knot.
+const KNOT_LENGTH: usize = 6;
+
+pub struct KnotPlugin;
+
+impl Plugin for KnotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Exploring), spawn_knotted_tree);
+        app.add_systems(
+            OnExit(AppState::Exploring),
+            abandon_knot_puzzle.run_if(in_state(InGame).and(resource_exists::<KnotPuzzle>)),
+        );
+        app.add_systems(OnExit(InGame), clear_knot_puzzle);
+        app.add_systems(
+            OnEnter(Paused),
+            disable_context::<KnotContext>.run_if(resource_exists::<KnotPuzzle>),
+        );
+        app.add_systems(
+            OnExit(Paused),
+            enable_context::<KnotContext>.run_if(resource_exists::<KnotPuzzle>),
+        );
+        app.add_systems(
+            Update,
+            (
+                pull_knot_with_cursor.run_if(not(in_state(Paused))),
+                update_knot_sequence_text.run_if(resource_changed::<KnotPuzzle>),
+            )
+                .run_if(resource_exists::<KnotPuzzle>),
+        );
+
+        app.add_observer(open_knot_puzzle);
+        app.add_observer(pull_knot_with_navigate);
+        app.add_observer(abandon_knot_puzzle_with_back);
+        app.add_observer(on_knot_pulled);
+        app.add_observer(on_knot_puzzle_outcome);
+    }
+}
+
+/// The rope tied around the tree. Interacting with it opens the knot puzzle.
+#[derive(Component)]
+pub struct KnottedRope;
+
+#[derive(Event)]
+struct KnotPulled(KnotPull);
+
+#[derive(Component)]
+struct KnotPuzzleRoot;
+
+#[derive(Component)]
+struct KnotSequenceText;
+
+/// The tree stands against the corridor's north wall, and its rope is strung down to the south one.
+const TREE_POSITION: Vec2 = Vec2::new(240.0, 96.0);
+const TREE_SIZE: Vec2 = Vec2::new(16.0, 48.0);
+const TREE_COLOR: Color = Color::srgb(0.35, 0.25, 0.18);
+const ROPE_COLOR: Color = Color::srgb(0.8, 0.7, 0.45);
+const ROPE_BARRIER_SIZE: Vec2 = Vec2::new(4.0, 192.0);
+
+/// The rope strung across the corridor, keeping the player from the raven until the knot is
+/// untied.
+#[derive(Component)]
+struct RopeBarrier;
+
+fn spawn_knotted_tree(mut commands: Commands, run_state: Res<RunState>) {
+    let mut rope = commands.spawn((
+        KnottedRope,
+        Sprite::from_color(ROPE_COLOR, Vec2::new(20.0, 4.0)),
+        // At the foot of the tree, where the rope is tied.
+        Transform::from_xyz(0.0, -TREE_SIZE.y / 2.0, 0.1),
+    ));
+
+    if !run_state.is_set(RunFlag::KnotUntied) {
+        rope.insert((
+            Interactable {
+                prompt: "prompt.untie_knot".to_string(),
+                radius: 24.0,
+            },
+            SlowdownField {
+                radius: 160.0,
+                strength: 0.85,
+                falloff: SlowdownFalloff::SmoothStep,
+                floor: Some(0.15),
+            },
+        ));
+    }
+
+    let rope = rope.id();
+
+    commands
+        .spawn((
+            DespawnOnExit(AppState::Exploring),
+            Sprite::from_color(TREE_COLOR, TREE_SIZE),
+            Transform::from_translation(TREE_POSITION.extend(0.0)),
+            RigidBody::Static,
+            Collider::rectangle(TREE_SIZE.x, TREE_SIZE.y),
+        ))
+        .add_child(rope);
+
+    if !run_state.is_set(RunFlag::KnotUntied) {
+        let rope_bottom = TREE_POSITION.y - TREE_SIZE.y / 2.0 - ROPE_BARRIER_SIZE.y;
+
+        commands.spawn((
+            RopeBarrier,
+            DespawnOnExit(AppState::Exploring),
+            Sprite::from_color(ROPE_COLOR, ROPE_BARRIER_SIZE),
+            Transform::from_xyz(
+                TREE_POSITION.x,
+                rope_bottom + ROPE_BARRIER_SIZE.y / 2.0,
+                -0.1,
+            ),
+            RigidBody::Static,
+            Collider::rectangle(ROPE_BARRIER_SIZE.x, ROPE_BARRIER_SIZE.y),
+        ));
+    }
+}
+
+fn open_knot_puzzle(
+    interacted: On<Interacted>,
+    mut commands: Commands,
+    ropes: Query<(), With<KnottedRope>>,
+    knot_puzzle: Option<Res<KnotPuzzle>>,
+    run_seed: Res<RunSeed>,
+) {
+    if !ropes.contains(interacted.target) || knot_puzzle.is_some() {
+        return;
+    }
+
+    let knot_puzzle = KnotPuzzle::new(&mut run_seed.fork("knot"), KNOT_LENGTH);
+
+    commands.spawn((
+        KnotPuzzleRoot,
+        DespawnOnExit(AppState::Exploring),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(16.0),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK.with_alpha(0.6)),
+        children![
+            (
+                KnotSequenceText,
+                Text::new(knot_puzzle.describe()),
+                TextFont::from_font_size(32.0),
+            ),
+            (
+                LocalizedText::new("knot.instructions"),
+                TextFont::from_font_size(14.0),
+            ),
+        ],
+    ));
+
+    commands.insert_resource(knot_puzzle);
+    commands.run_system_cached(disable_context::<Player>);
+    commands.run_system_cached(enable_context::<KnotContext>);
+}
+
+fn pull_knot_with_navigate(
+    navigate: On<Start<ui::Navigate>>,
+    mut commands: Commands,
+    knot_contexts: Query<(), With<KnotContext>>,
+) {
+    if knot_contexts.contains(navigate.context)
+        && let Some(pull) = KnotPull::from_direction(navigate.value)
+    {
+        commands.trigger(KnotPulled(pull));
+    }
+}
+
+fn pull_knot_with_cursor(
+    mut commands: Commands,
+    mut drag_start: Local<Option<Vec2>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    cursor: Cursor,
+) {
+    /// Shortest drag, in world units, that counts as a pull.
+    const DRAG_THRESHOLD: f32 = 4.0;
+
+    if mouse_buttons.just_pressed(MouseButton::Left) {
+        *drag_start = cursor.world_position();
+    }
+
+    if mouse_buttons.just_released(MouseButton::Left)
+        && let Some(start) = drag_start.take()
+        && let Some(end) = cursor.world_position()
+        && let Some(pull) = KnotPull::from_direction((end - start) / DRAG_THRESHOLD)
+    {
+        commands.trigger(KnotPulled(pull));
+    }
+}
+
+fn on_knot_pulled(
+    pulled: On<KnotPulled>,
+    mut commands: Commands,
+    knot_puzzle: Option<ResMut<KnotPuzzle>>,
+) {
+    let Some(mut knot_puzzle) = knot_puzzle else {
+        return;
+    };
+
+    match knot_puzzle.pull(pulled.0) {
+        KnotPullResult::Loosened | KnotPullResult::Tightened => {}
+        KnotPullResult::Untied => commands.trigger(KnotPuzzleOutcome::Untied),
+    }
+}
+
+fn update_knot_sequence_text(
+    sequence_text: Single<&mut Text, With<KnotSequenceText>>,
+    knot_puzzle: Res<KnotPuzzle>,
+) {
+    sequence_text.into_inner().0 = knot_puzzle.describe();
+}
+
+fn abandon_knot_puzzle_with_back(
+    back: On<Start<ui::Back>>,
+    mut commands: Commands,
+    knot_contexts: Query<(), With<KnotContext>>,
+) {
+    if knot_contexts.contains(back.context) {
+        commands.trigger(KnotPuzzleOutcome::Abandoned);
+    }
+}
+
+/// Gives up on the open puzzle when the run moves on without the player finishing it.
+fn abandon_knot_puzzle(mut commands: Commands) {
+    commands.trigger(KnotPuzzleOutcome::Abandoned);
+}
+
+/// Drops the open puzzle, if any, when the run ends. Nothing is recorded, and the player context
+/// goes away with the player.
+fn clear_knot_puzzle(mut commands: Commands) {
+    commands.remove_resource::<KnotPuzzle>();
+    commands.run_system_cached(disable_context::<KnotContext>);
+}
+
+fn on_knot_puzzle_outcome(
+    outcome: On<KnotPuzzleOutcome>,
+    mut commands: Commands,
+    mut run_state: ResMut<RunState>,
+    knot_puzzle_roots: Query<Entity, With<KnotPuzzleRoot>>,
+    ropes: Query<Entity, With<KnottedRope>>,
+    rope_barriers: Query<Entity, With<RopeBarrier>>,
+) {
+    commands.remove_resource::<KnotPuzzle>();
+    commands.run_system_cached(disable_context::<KnotContext>);
+    commands.run_system_cached(enable_context::<Player>);
+
+    for entity in &knot_puzzle_roots {
+        commands.entity(entity).despawn();
+    }
+
+    commands.trigger(RecordChoice(RunChoice::Knot {
+        untied: *outcome == KnotPuzzleOutcome::Untied,
+    }));
+
+    if *outcome == KnotPuzzleOutcome::Untied {
+        run_state.set(RunFlag::KnotUntied);
+
+        // With the knot gone there's nothing left to hold the player back.
+        for entity in &ropes {
+            commands
+                .entity(entity)
+                .remove::<(Interactable, SlowdownField)>();
+        }
+
+        for entity in &rope_barriers {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Progress through the sequence of pulls that unties the knot.
+#[derive(Resource)]
+pub struct KnotPuzzle {
+    sequence: Vec<KnotPull>,
+    progress: usize,
+}
+
+impl KnotPuzzle {
+    pub fn new(rng: &mut impl Rng, length: usize) -> Self {
+        Self {
+            sequence: (0..length)
+                .map(|_| KnotPull::ALL[rng.random_range(0..KnotPull::ALL.len())])
+                .collect(),
+            progress: 0,
+        }
+    }
+
+    /// Applies a pull to the knot. A wrong pull tightens it back and the sequence starts over.
+    pub fn pull(&mut self, pull: KnotPull) -> KnotPullResult {
+        if self.sequence.get(self.progress) != Some(&pull) {
+            self.progress = 0;
+            return KnotPullResult::Tightened;
+        }
+
+        self.progress += 1;
+
+        if self.progress == self.sequence.len() {
+            KnotPullResult::Untied
+        } else {
+            KnotPullResult::Loosened
+        }
+    }
+
+    /// Returns the sequence as text, with the pulls already made replaced by dashes.
+    pub fn describe(&self) -> String {
+        self.sequence
+            .iter()
+            .enumerate()
+            .map(|(index, pull)| match index < self.progress {
+                true => "-",
+                false => pull.symbol(),
+            })
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KnotPull {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl KnotPull {
+    const ALL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    /// Returns the pull closest to `direction`, or `None` if it's too short to tell.
+    pub fn from_direction(direction: Vec2) -> Option<Self> {
+        if direction.length_squared() < 0.25 {
+            return None;
+        }
+
+        Some(if direction.x.abs() > direction.y.abs() {
+            if direction.x > 0.0 {
+                Self::Right
+            } else {
+                Self::Left
+            }
+        } else if direction.y > 0.0 {
+            Self::Up
+        } else {
+            Self::Down
+        })
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Up => "^",
+            Self::Down => "v",
+            Self::Left => "<",
+            Self::Right => ">",
+        }
+    }
+}
+
+pub enum KnotPullResult {
+    Loosened,
+    Tightened,
+    Untied,
+}
+
+/// Triggered when the knot puzzle closes.
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KnotPuzzleOutcome {
+    Untied,
+    Abandoned,
+}
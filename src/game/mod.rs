@@ -1,4 +1,18 @@
+mod dialogue;
+mod ending;
+mod falling;
+pub mod interaction;
+pub mod journal;
+pub mod knot;
+mod mood;
+pub mod npc;
+pub mod objects;
 pub mod player;
 mod plugin;
+pub mod raven;
+pub mod rooms;
+pub mod run;
+pub mod seed;
+pub mod slowdown;
 
 pub use plugin::GamePlugin;
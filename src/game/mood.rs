@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::{
+    audio::MusicParameters,
+    game::{
+        rooms::{RoomId, RoomRecord},
+        run::{RunFlag, RunState},
+    },
+};
+
+/// Rises from 0 to 1 as the player visits the rooms.
+const PROGRESS_PARAMETER: &str = "progress";
+/// Becomes 1 once the knot is untied.
+const KNOT_PARAMETER: &str = "knot";
+
+pub struct MoodPlugin;
+
+impl Plugin for MoodPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            update_music_parameters
+                .run_if(resource_changed::<RunState>.or(resource_changed::<RoomRecord>)),
+        );
+    }
+}
+
+/// Feeds the run's progress to the music, so its stems can follow along.
+fn update_music_parameters(
+    mut music_parameters: ResMut<MusicParameters>,
+    run_state: Res<RunState>,
+    room_record: Res<RoomRecord>,
+) {
+    let visited_rooms = RoomId::all()
+        .filter(|room| room_record.is_visited(*room))
+        .count();
+
+    music_parameters.set(
+        PROGRESS_PARAMETER,
+        visited_rooms as f32 / RoomId::all().count() as f32,
+    );
+    music_parameters.set(
+        KNOT_PARAMETER,
+        if run_state.is_set(RunFlag::KnotUntied) {
+            1.0
+        } else {
+            0.0
+        },
+    );
+}
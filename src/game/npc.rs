@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::animation::SpriteAnimation;
+
+/// Seconds each frame of an NPC's idle animation is shown for.
+const IDLE_FRAME_SECS: f32 = 0.25;
+
+/// A character the player can talk to. Spawn it through [`npc`] so it idles in place.
+#[derive(Component)]
+pub struct Npc {
+    /// Key of the name shown above everything the NPC says.
+    pub name: String,
+}
+
+/// Returns the components of an NPC whose idle animation loops over the first `idle_frames`
+/// frames of `layout`.
+pub fn npc(
+    name: impl Into<String>,
+    image: Handle<Image>,
+    layout: Handle<TextureAtlasLayout>,
+    idle_frames: usize,
+) -> impl Bundle {
+    (
+        Npc { name: name.into() },
+        Sprite {
+            image,
+            texture_atlas: Some(layout.into()),
+            ..default()
+        },
+        SpriteAnimation::new(
+            (0..idle_frames)
+                .map(|index| (index, IDLE_FRAME_SECS).into())
+                .collect::<Vec<_>>(),
+        ),
+    )
+}
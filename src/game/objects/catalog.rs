@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    assets::{AssetCategory, AssetCollection, AssetManifest},
+    audio::Rolloff,
+};
+
+/// Every object that may show up in a room, along with the rules for drawing them.
+#[derive(Asset, TypePath, Deserialize)]
+pub struct ObjectCatalog {
+    pub objects_per_room: usize,
+    /// Maximum number of objects with a given tag drawn in a single run. Tags not listed here have
+    /// no limit.
+    #[serde(default)]
+    pub tag_limits: BTreeMap<String, usize>,
+    pub objects: Vec<CatalogObject>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct CatalogObject {
+    pub name: String,
+    /// Logical name of the texture in the asset manifest.
+    pub texture: String,
+    /// Key of the text shown when the player is close enough to interact with the object.
+    pub interaction: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Relative chance of drawing this object over the others still available. Objects with a
+    /// weight of `0` are never drawn.
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    #[serde(default)]
+    pub sound: Option<ObjectSound>,
+}
+
+/// A sound an object plays on a loop, heard from around it, such as a clock's ticking.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ObjectSound {
+    /// Name of the file in the asset manifest's sfx.
+    pub sfx: String,
+    /// In decibels.
+    #[serde(default)]
+    pub volume: f32,
+    /// Distance, in pixels, past which the sound can't be heard.
+    pub max_distance: f32,
+    #[serde(default)]
+    pub rolloff: Rolloff,
+    #[serde(default)]
+    pub panning_width: Option<f32>,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+impl ObjectCatalog {
+    /// Draws the objects of `room_count` rooms, in order. No object is drawn twice in the same
+    /// run, and rooms get fewer objects once the catalog runs out of candidates.
+    pub fn draw(&self, rng: &mut impl Rng, room_count: usize) -> Vec<Vec<&CatalogObject>> {
+        let mut drawn = vec![false; self.objects.len()];
+        let mut tag_counts = BTreeMap::<&str, usize>::new();
+
+        (0..room_count)
+            .map(|_| {
+                let mut room = Vec::with_capacity(self.objects_per_room);
+
+                for _ in 0..self.objects_per_room {
+                    let candidates = self
+                        .objects
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, object)| {
+                            !drawn[*index]
+                                && object.weight > 0
+                                && object.tags.iter().all(|tag| {
+                                    self.tag_limits.get(tag).is_none_or(|limit| {
+                                        tag_counts.get(tag.as_str()).copied().unwrap_or(0) < *limit
+                                    })
+                                })
+                        })
+                        .collect::<Vec<(usize, &CatalogObject)>>();
+
+                    let total_weight = candidates.iter().map(|(_, object)| object.weight).sum();
+
+                    if total_weight == 0 {
+                        break;
+                    }
+
+                    let mut roll = draw_below(rng, total_weight);
+
+                    for (index, object) in candidates {
+                        if roll >= object.weight {
+                            roll -= object.weight;
+                            continue;
+                        }
+
+                        drawn[index] = true;
+
+                        for tag in &object.tags {
+                            *tag_counts.entry(tag.as_str()).or_default() += 1;
+                        }
+
+                        room.push(object);
+                        break;
+                    }
+                }
+
+                room
+            })
+            .collect()
+    }
+}
+
+/// Returns a number from `0` up to, but not including, `bound`.
+///
+/// This reads the generator's output directly instead of going through `rand`'s distributions,
+/// whose algorithms may change between versions, so a given seed always yields the same draw.
+fn draw_below(rng: &mut impl Rng, bound: u32) -> u32 {
+    ((u64::from(rng.next_u32()) * u64::from(bound)) >> 32) as u32
+}
+
+pub struct ObjectCatalogs;
+
+impl AssetCategory for ObjectCatalogs {
+    type Asset = ObjectCatalog;
+
+    const NAME: &'static str = "catalogs";
+
+    fn entries(manifest: &AssetManifest) -> &BTreeMap<String, String> {
+        &manifest.catalogs
+    }
+}
+
+pub type ObjectCatalogHandles = AssetCollection<ObjectCatalogs>;
+
+#[cfg(test)]
+mod tests {
+    use crate::game::seed::RunSeed;
+
+    use super::*;
+
+    const CATALOG: &str = r#"(
+        objects_per_room: 2,
+        tag_limits: { "light": 1 },
+        objects: [
+            (name: "mirror", texture: "", interaction: "", weight: 3),
+            (name: "lamp", texture: "", interaction: "", tags: ["light"], weight: 2),
+            (name: "candle", texture: "", interaction: "", tags: ["light"]),
+            (name: "clock", texture: "", interaction: "", weight: 2),
+            (name: "chair", texture: "", interaction: ""),
+            (name: "letter", texture: "", interaction: "", weight: 4),
+            (name: "doll", texture: "", interaction: ""),
+            (name: "painting", texture: "", interaction: "", weight: 2),
+        ],
+    )"#;
+
+    fn draw_names(choices: [bool; 3], room_count: usize) -> Vec<Vec<String>> {
+        let catalog = ron::from_str::<ObjectCatalog>(CATALOG).unwrap();
+        let mut rng = RunSeed::from_choices(choices).fork("objects");
+
+        catalog
+            .draw(&mut rng, room_count)
+            .into_iter()
+            .map(|room| room.into_iter().map(|object| object.name.clone()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn draw_is_pinned_for_a_fixed_seed() {
+        assert_eq!(
+            draw_names([true, false, true], 3),
+            [
+                ["chair", "letter"],
+                ["mirror", "painting"],
+                ["doll", "clock"],
+            ]
+        );
+        assert_eq!(
+            draw_names([false, false, false], 3),
+            [["clock", "doll"], ["lamp", "mirror"], ["chair", "painting"],]
+        );
+    }
+
+    #[test]
+    fn draw_runs_out_without_repeats_or_breaking_tag_limits() {
+        assert_eq!(
+            draw_names([true, false, true], 5),
+            [
+                vec!["chair", "letter"],
+                vec!["mirror", "painting"],
+                vec!["doll", "clock"],
+                vec!["candle"],
+                vec![],
+            ]
+        );
+    }
+}
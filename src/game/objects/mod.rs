@@ -0,0 +1,5 @@
+mod catalog;
+mod plugin;
+
+pub use catalog::*;
+pub(super) use plugin::*;
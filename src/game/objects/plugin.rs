@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+
+use crate::{
+    assets::{AssetCollectionAppExt, RonAssetPlugin},
+    audio::{EmitterLoop, SpatialEmitter},
+    game::{
+        interaction::{Interactable, Interacted},
+        journal::{RecordChoice, RunChoice},
+        rooms::{DOOR_COUNT, RoomEntered, RoomId},
+        seed::RunSeed,
+    },
+    state::AppState,
+    textures::TextureHandles,
+};
+
+use super::{CatalogObject, ObjectCatalog, ObjectCatalogHandles, ObjectCatalogs};
+
+const OBJECT_CATALOG_NAME: &str = "rooms";
+
+pub struct ObjectsPlugin;
+
+impl Plugin for ObjectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<ObjectCatalog>::new(&["catalog.ron"]));
+        app.add_asset_collection::<ObjectCatalogs>();
+
+        app.add_systems(OnEnter(AppState::Exploring), draw_room_objects);
+
+        app.add_observer(spawn_room_objects);
+        app.add_observer(record_object_interaction);
+    }
+}
+
+/// An object drawn from the catalog, placed in the given room.
+#[derive(Component)]
+pub struct RoomObject {
+    pub room: RoomId,
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
+/// Objects drawn for each room this run, indexed by room.
+#[derive(Resource, Deref)]
+struct RoomObjects(Vec<Vec<CatalogObject>>);
+
+const OBJECT_SPACING: f32 = 64.0;
+/// Vertical offset, in pixels, from the center of the room to the row of objects.
+const OBJECT_ROW_OFFSET: f32 = 24.0;
+
+fn draw_room_objects(
+    mut commands: Commands,
+    catalog_handles: Res<ObjectCatalogHandles>,
+    catalogs: Res<Assets<ObjectCatalog>>,
+    run_seed: Res<RunSeed>,
+) {
+    let Some(catalog) = catalog_handles
+        .get(OBJECT_CATALOG_NAME)
+        .and_then(|handle| catalogs.get(&handle))
+    else {
+        commands.remove_resource::<RoomObjects>();
+        return;
+    };
+
+    let rooms = catalog
+        .draw(&mut run_seed.fork("objects"), usize::from(DOOR_COUNT))
+        .into_iter()
+        .map(|room| room.into_iter().cloned().collect())
+        .collect();
+
+    commands.insert_resource(RoomObjects(rooms));
+}
+
+fn spawn_room_objects(
+    entered: On<RoomEntered>,
+    mut commands: Commands,
+    room_objects: Option<Res<RoomObjects>>,
+    texture_handles: Res<TextureHandles>,
+) {
+    let RoomEntered(room) = *entered;
+    let Some(objects) = room_objects
+        .as_ref()
+        .and_then(|room_objects| room_objects.get(usize::from(room.0)))
+    else {
+        return;
+    };
+
+    for (index, object) in objects.iter().enumerate() {
+        let offset = index as f32 - (objects.len() - 1) as f32 / 2.0;
+        let position = room.center() + Vec2::new(offset * OBJECT_SPACING, OBJECT_ROW_OFFSET);
+
+        let mut room_object = commands.spawn((
+            RoomObject {
+                room,
+                name: object.name.clone(),
+                tags: object.tags.clone(),
+            },
+            DespawnOnExit(AppState::Exploring),
+            Sprite::from_image(texture_handles.get(&object.texture).unwrap_or_default()),
+            Transform::from_translation(position.extend(-0.5)),
+            Interactable {
+                prompt: object.interaction.clone(),
+                radius: 20.0,
+            },
+        ));
+
+        if let Some(sound) = &object.sound {
+            let mut emitter = SpatialEmitter::new(sound.max_distance).with_rolloff(sound.rolloff);
+
+            if let Some(panning_width) = sound.panning_width {
+                emitter = emitter.with_panning_width(panning_width);
+            }
+
+            room_object.insert((
+                emitter,
+                EmitterLoop::new(&sound.sfx).with_volume(sound.volume),
+            ));
+        }
+    }
+}
+
+fn record_object_interaction(
+    interacted: On<Interacted>,
+    mut commands: Commands,
+    room_objects: Query<&RoomObject>,
+) {
+    if let Ok(room_object) = room_objects.get(interacted.target) {
+        commands.trigger(RecordChoice(RunChoice::ObjectInteracted {
+            room: room_object.room,
+            object: room_object.name.clone(),
+            tags: room_object.tags.clone(),
+        }));
+    }
+}
@@ -33,16 +33,18 @@ fn bind_player_actions(add: On<Add, Player>, mut commands: Commands) {
             )),
             Spawn((
                 Action::<Walk>::new(),
+                // Doors, room exits and objects sit above and below the middle of the corridor, so
+                // walking goes both ways.
                 Bindings::spawn((
-                    Axial::new(GamepadAxis::LeftStickX, Binding::None).with((
+                    Axial::left_stick().with((
                         DeadZone {
                             lower_threshold: 0.1,
                             ..Default::default()
                         },
                         SmoothNudge::default(),
                     )),
-                    Bidirectional::new(KeyCode::ArrowRight, KeyCode::ArrowLeft),
-                    Bidirectional::new(KeyCode::KeyD, KeyCode::KeyA),
+                    Cardinal::arrows(),
+                    Cardinal::wasd_keys(),
                 )),
             )),
             Spawn((
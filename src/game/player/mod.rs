@@ -2,9 +2,11 @@ use bevy::ecs::component::Component;
 
 pub mod actions;
 mod input;
+mod movement;
 mod plugin;
 
 pub(super) use input::*;
+pub(super) use movement::*;
 pub(super) use plugin::*;
 
 #[derive(Component)]
@@ -0,0 +1,86 @@
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::Action;
+
+use crate::{
+    animation::{SpriteAnimation, SpriteFacing},
+    audio::PlaySoundCue,
+    physics::{LENGTH_UNIT, Speed, SpeedMultiplier},
+    state::AppState,
+};
+
+use super::{Player, actions::Walk};
+
+/// Seconds between footsteps while walking.
+const STRIDE_SECS: f32 = 0.35;
+
+pub struct PlayerMovementPlugin;
+
+impl Plugin for PlayerMovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (walk, update_facing, flip_sprite_to_facing, play_footsteps)
+                .chain()
+                .run_if(in_state(AppState::Exploring)),
+        );
+        app.add_systems(OnExit(AppState::Exploring), stop_walking);
+    }
+}
+
+fn walk(
+    player: Single<(&mut LinearVelocity, &Speed, &SpeedMultiplier), With<Player>>,
+    walk_action: Single<&Action<Walk>>,
+) {
+    let (mut linear_velocity, speed, speed_multiplier) = player.into_inner();
+    let direction = walk_action.clamp_length_max(1.0);
+    linear_velocity.0 = direction * speed.effective(speed_multiplier) * LENGTH_UNIT;
+}
+
+fn update_facing(player: Single<(&LinearVelocity, &mut SpriteFacing), With<Player>>) {
+    let (linear_velocity, mut sprite_facing) = player.into_inner();
+
+    if let Ok(direction) = Dir2::new(linear_velocity.0) {
+        sprite_facing.set_if_neq(direction.into());
+    }
+}
+
+fn flip_sprite_to_facing(
+    player: Single<(&SpriteFacing, &mut SpriteAnimation), (With<Player>, Changed<SpriteFacing>)>,
+) {
+    let (sprite_facing, mut sprite_animation) = player.into_inner();
+
+    // The sheet has no frames facing up or down, so walking straight that way keeps the last side.
+    if matches!(sprite_facing, SpriteFacing::North | SpriteFacing::South) {
+        return;
+    }
+
+    // The player's sprite sheet is drawn facing east.
+    sprite_animation.flip_x = sprite_facing.is_westward();
+}
+
+fn play_footsteps(
+    mut commands: Commands,
+    player: Single<&LinearVelocity, With<Player>>,
+    time: Res<Time>,
+    mut since_last_step: Local<f32>,
+) {
+    // Standing still readies the next step, so walking off plays one right away.
+    if player.0 == Vec2::ZERO {
+        *since_last_step = STRIDE_SECS;
+        return;
+    }
+
+    *since_last_step += time.delta_secs();
+
+    if *since_last_step >= STRIDE_SECS {
+        *since_last_step = 0.0;
+        commands.trigger(PlaySoundCue::new("footstep"));
+    }
+}
+
+fn stop_walking(mut players: Query<&mut LinearVelocity, With<Player>>) {
+    for mut linear_velocity in &mut players {
+        linear_velocity.0 = Vec2::ZERO;
+    }
+}
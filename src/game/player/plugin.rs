@@ -1,6 +1,13 @@
+use avian2d::prelude::{Collider, CollisionLayers, LockedAxes, RigidBody};
 use bevy::prelude::*;
 
-use crate::animation::SpriteAnimation;
+use crate::{
+    animation::{SpriteAnimation, SpriteFacing},
+    camera::MainCameraTarget,
+    physics::{CollisionLayer, Speed},
+    state::{AppState, InGame},
+    textures::TextureHandles,
+};
 
 use super::Player;
 
@@ -8,22 +15,36 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(PostStartup, spawn_player);
+        app.add_systems(OnEnter(AppState::Falling), spawn_player);
         app.init_resource::<PlayerTextureAtlasLayout>();
     }
 }
 
 fn spawn_player(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    texture_handles: Res<TextureHandles>,
     player_layout: Res<PlayerTextureAtlasLayout>,
 ) {
     const FRAME_SPEED: f32 = 0.05;
+    const COLLIDER_RADIUS: f32 = 6.0;
+    /// Walking speed, in meters per second.
+    const WALK_SPEED: f32 = 3.0;
 
     commands.spawn((
         Player,
+        DespawnOnExit(InGame),
+        MainCameraTarget,
+        Speed(WALK_SPEED),
+        SpriteFacing::East,
+        RigidBody::Dynamic,
+        LockedAxes::ROTATION_LOCKED,
+        Collider::circle(COLLIDER_RADIUS),
+        CollisionLayers::new(
+            CollisionLayer::Player,
+            [CollisionLayer::Default, CollisionLayer::Interactable],
+        ),
         Sprite {
-            image: asset_server.load("textures/bevyJam-player-running.png"),
+            image: texture_handles.get("player_running").unwrap_or_default(),
             texture_atlas: Some(player_layout.clone().into()),
             ..Default::default()
         },
@@ -1,6 +1,21 @@
 use bevy::app::{PluginGroup, PluginGroupBuilder};
 
-use crate::game::player::{PlayerInputPlugin, PlayerPlugin};
+use crate::game::{
+    dialogue::DialoguePlugin,
+    ending::EndingPlugin,
+    falling::FallingPlugin,
+    interaction::InteractionPlugin,
+    journal::RunJournalPlugin,
+    knot::{KnotInputPlugin, KnotPlugin},
+    mood::MoodPlugin,
+    objects::ObjectsPlugin,
+    player::{PlayerInputPlugin, PlayerMovementPlugin, PlayerPlugin},
+    raven::RavenPlugin,
+    rooms::RoomsPlugin,
+    run::RunStatePlugin,
+    seed::RunSeedPlugin,
+    slowdown::SlowdownPlugin,
+};
 
 pub struct GamePlugin;
 
@@ -9,5 +24,20 @@ impl PluginGroup for GamePlugin {
         PluginGroupBuilder::start::<Self>()
             .add(PlayerPlugin)
             .add(PlayerInputPlugin)
+            .add(PlayerMovementPlugin)
+            .add(RunSeedPlugin)
+            .add(FallingPlugin)
+            .add(InteractionPlugin)
+            .add(SlowdownPlugin)
+            .add(RunStatePlugin)
+            .add(RunJournalPlugin)
+            .add(KnotPlugin)
+            .add(KnotInputPlugin)
+            .add(RoomsPlugin)
+            .add(ObjectsPlugin)
+            .add(DialoguePlugin)
+            .add(RavenPlugin)
+            .add(EndingPlugin)
+            .add(MoodPlugin)
     }
 }
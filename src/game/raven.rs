@@ -0,0 +1,235 @@
+use avian2d::prelude::{Collider, CollidingEntities, CollisionLayers, RigidBody, Sensor};
+use bevy::prelude::*;
+
+use crate::{
+    audio::{PlaySoundCue, SpatialEmitter},
+    game::{
+        dialogue::{
+            DialogueChoiceSelected, DialogueScriptHandles, DialogueVariables, StartDialogue,
+        },
+        interaction::{Interactable, Interacted},
+        journal::{RecordChoice, RunChoice, RunJournal},
+        npc::{Npc, npc},
+        player::Player,
+        run::{RunFlag, RunState},
+    },
+    physics::CollisionLayer,
+    state::AppState,
+    textures::TextureHandles,
+};
+
+const RAVEN_DIALOGUE_NAME: &str = "raven";
+const RAVEN_START_NODE: &str = "start";
+/// Node of the raven's dialogue offering to cross the golden bridge.
+const GOLDEN_BRIDGE_QUESTION_NODE: &str = "question";
+
+pub struct RavenPlugin;
+
+impl Plugin for RavenPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RavenTextureAtlasLayout>();
+
+        app.add_systems(
+            OnEnter(AppState::Exploring),
+            (spawn_raven, spawn_golden_bridge),
+        );
+        app.add_systems(
+            Update,
+            cross_golden_bridge.run_if(in_state(AppState::Exploring)),
+        );
+
+        app.add_observer(talk_to_raven);
+        app.add_observer(answer_raven);
+    }
+}
+
+/// The raven waiting at the end of the corridor, in front of the golden bridge.
+#[derive(Component)]
+pub struct Raven;
+
+/// Blocks the way onto the golden bridge until the player agrees to cross it.
+#[derive(Component)]
+struct GoldenBridgeGate;
+
+#[derive(Component)]
+struct GoldenBridgeEnd;
+
+#[derive(Resource, Deref)]
+struct RavenTextureAtlasLayout(Handle<TextureAtlasLayout>);
+
+impl FromWorld for RavenTextureAtlasLayout {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let atlas =
+            TextureAtlasLayout::from_grid(uvec2(16, 16), RAVEN_FRAMES as u32, 1, None, None);
+        Self(asset_server.add(atlas))
+    }
+}
+
+const RAVEN_FRAMES: usize = 4;
+const RAVEN_POSITION: Vec2 = Vec2::new(296.0, 40.0);
+/// Distance, in pixels, past which the raven's caws can't be heard.
+const RAVEN_HEARING_DISTANCE: f32 = 240.0;
+/// The bridge runs from the gate to the east end of the corridor.
+const GOLDEN_BRIDGE_GATE_X: f32 = 320.0;
+const GOLDEN_BRIDGE_END_X: f32 = 388.0;
+const GOLDEN_BRIDGE_SIZE: Vec2 = Vec2::new(80.0, 48.0);
+const GOLDEN_BRIDGE_COLOR: Color = Color::srgb(0.85, 0.68, 0.2);
+const GATE_SIZE: Vec2 = Vec2::new(8.0, 240.0);
+const GATE_COLOR: Color = Color::srgb(0.3, 0.26, 0.2);
+
+fn spawn_raven(
+    mut commands: Commands,
+    texture_handles: Res<TextureHandles>,
+    raven_layout: Res<RavenTextureAtlasLayout>,
+    run_journal: Res<RunJournal>,
+) {
+    let mut raven = commands.spawn((
+        Raven,
+        DespawnOnExit(AppState::Exploring),
+        npc(
+            "npc.raven",
+            texture_handles.get("raven").unwrap_or_default(),
+            raven_layout.clone(),
+            RAVEN_FRAMES,
+        ),
+        Transform::from_translation(RAVEN_POSITION.extend(0.0)),
+        SpatialEmitter::new(RAVEN_HEARING_DISTANCE),
+    ));
+
+    // The raven only asks once per run.
+    if !run_journal
+        .choices()
+        .any(|choice| matches!(choice, RunChoice::RavenAnswered { .. }))
+    {
+        raven.insert(Interactable {
+            prompt: "prompt.talk_to_raven".to_string(),
+            radius: 24.0,
+        });
+    }
+}
+
+fn spawn_golden_bridge(mut commands: Commands, run_state: Res<RunState>) {
+    let bridge_center_x = GOLDEN_BRIDGE_GATE_X + GOLDEN_BRIDGE_SIZE.x / 2.0;
+
+    commands.spawn((
+        DespawnOnExit(AppState::Exploring),
+        Sprite::from_color(GOLDEN_BRIDGE_COLOR, GOLDEN_BRIDGE_SIZE),
+        Transform::from_xyz(bridge_center_x, 0.0, -1.5),
+    ));
+
+    commands.spawn((
+        GoldenBridgeEnd,
+        DespawnOnExit(AppState::Exploring),
+        Transform::from_xyz(GOLDEN_BRIDGE_END_X, 0.0, 0.0),
+        Sensor,
+        Collider::circle(12.0),
+        CollisionLayers::new(CollisionLayer::Interactable, CollisionLayer::Player),
+        CollidingEntities::default(),
+    ));
+
+    if !run_state.is_set(RunFlag::GoldenBridgeOpened) {
+        commands.spawn((
+            GoldenBridgeGate,
+            DespawnOnExit(AppState::Exploring),
+            Sprite::from_color(GATE_COLOR, GATE_SIZE),
+            Transform::from_xyz(GOLDEN_BRIDGE_GATE_X, 0.0, -1.0),
+            RigidBody::Static,
+            Collider::rectangle(GATE_SIZE.x, GATE_SIZE.y),
+        ));
+    }
+}
+
+fn talk_to_raven(
+    interacted: On<Interacted>,
+    mut commands: Commands,
+    ravens: Query<&Npc, With<Raven>>,
+    dialogue_handles: Res<DialogueScriptHandles>,
+    run_journal: Res<RunJournal>,
+) {
+    let Ok(raven) = ravens.get(interacted.target) else {
+        return;
+    };
+
+    commands.trigger(PlaySoundCue::new("raven_caw").with_emitter(interacted.target));
+
+    // The raven remarks on what the player did so far, so the script checks which of these are set.
+    let mut variables = DialogueVariables::new();
+
+    if let Some(pick) = run_journal.fall_pick(0) {
+        let fall_image = if pick { "raven.sun" } else { "raven.moon" };
+        variables.insert("fall_image".to_string(), fall_image.to_string());
+    }
+
+    if run_journal.rooms_without_interactions() > 0 {
+        variables.insert("rooms_untouched".to_string(), String::new());
+    }
+
+    if run_journal.interacted_with_tag("light") {
+        variables.insert("carried_light".to_string(), String::new());
+    }
+
+    commands.trigger(StartDialogue {
+        script: dialogue_handles
+            .get(RAVEN_DIALOGUE_NAME)
+            .unwrap_or_default(),
+        node: RAVEN_START_NODE.to_string(),
+        speaker: raven.name.clone(),
+        variables,
+    });
+}
+
+fn answer_raven(
+    selected: On<DialogueChoiceSelected>,
+    mut commands: Commands,
+    dialogue_handles: Res<DialogueScriptHandles>,
+    ravens: Query<Entity, With<Raven>>,
+    gates: Query<Entity, With<GoldenBridgeGate>>,
+    mut run_state: ResMut<RunState>,
+) {
+    let is_raven_question = dialogue_handles
+        .get(RAVEN_DIALOGUE_NAME)
+        .is_some_and(|handle| handle.id() == selected.script)
+        && selected.node == GOLDEN_BRIDGE_QUESTION_NODE;
+
+    if !is_raven_question {
+        return;
+    }
+
+    let yes = selected.choice == "yes";
+
+    for entity in &ravens {
+        commands.entity(entity).remove::<Interactable>();
+    }
+
+    if yes {
+        run_state.set(RunFlag::GoldenBridgeOpened);
+
+        for entity in &gates {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    commands.trigger(RecordChoice(RunChoice::RavenAnswered { yes }));
+}
+
+fn cross_golden_bridge(
+    mut run_state: ResMut<RunState>,
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+    bridge_ends: Query<&CollidingEntities, With<GoldenBridgeEnd>>,
+    player: Single<Entity, With<Player>>,
+) {
+    if run_state.is_set(RunFlag::GoldenBridgeCrossed) {
+        return;
+    }
+
+    if bridge_ends
+        .iter()
+        .any(|colliding_entities| colliding_entities.contains(&*player))
+    {
+        run_state.set(RunFlag::GoldenBridgeCrossed);
+        commands.trigger(RecordChoice(RunChoice::GoldenBridgeCrossed));
+        next_state.set(AppState::Ending);
+    }
+}
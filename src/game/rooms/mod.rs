@@ -0,0 +1,5 @@
+mod plugin;
+mod room;
+
+pub(super) use plugin::*;
+pub use room::*;
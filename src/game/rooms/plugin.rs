@@ -0,0 +1,332 @@
+use avian2d::prelude::{Collider, LinearVelocity, RigidBody};
+use bevy::prelude::*;
+
+use crate::{
+    audio::PlaySoundCue,
+    camera::{MainCamera, WorldBounds},
+    game::{
+        interaction::{Interactable, Interacted},
+        journal::{RecordChoice, RunChoice},
+        player::Player,
+    },
+    input::{disable_context, enable_context},
+    state::AppState,
+};
+
+use super::{ROOM_SIZE, RoomEntered, RoomExited, RoomId, RoomRecord};
+
+pub struct RoomsPlugin;
+
+impl Plugin for RoomsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RoomRecord>();
+
+        app.add_systems(OnEnter(AppState::Falling), reset_room_record);
+        app.add_systems(
+            OnEnter(AppState::Exploring),
+            (spawn_corridor, spawn_rooms, spawn_room_fade),
+        );
+        app.add_systems(OnExit(AppState::Exploring), clear_room_transition);
+        app.add_systems(
+            Update,
+            advance_room_transition.run_if(resource_exists::<RoomTransition>),
+        );
+
+        app.add_observer(open_door);
+        app.add_observer(leave_room);
+        app.add_observer(on_room_entered);
+        app.add_observer(on_room_exited);
+    }
+}
+
+/// A door along the corridor leading to the given room.
+#[derive(Component)]
+pub struct Door(pub RoomId);
+
+/// The way out of the given room, back to the corridor.
+#[derive(Component)]
+pub struct RoomExit(pub RoomId);
+
+/// Fade to black and back while the player is moved between the corridor and a room.
+#[derive(Resource)]
+struct RoomTransition {
+    destination: RoomTransitionDestination,
+    timer: Timer,
+    faded_out: bool,
+}
+
+#[derive(Clone, Copy)]
+enum RoomTransitionDestination {
+    Room(RoomId),
+    /// Back in front of the door of the room being left.
+    Corridor(RoomId),
+}
+
+#[derive(Component)]
+struct RoomFade;
+
+const CORRIDOR_SIZE: Vec2 = Vec2::new(800.0, 240.0);
+const CORRIDOR_COLOR: Color = Color::srgb(0.12, 0.1, 0.14);
+const ROOM_COLOR: Color = Color::srgb(0.16, 0.13, 0.18);
+const DOOR_SIZE: Vec2 = Vec2::new(16.0, 24.0);
+const DOOR_SPACING: f32 = 112.0;
+const DOOR_COLOR: Color = Color::srgb(0.55, 0.4, 0.25);
+const SEALED_DOOR_COLOR: Color = Color::srgb(0.25, 0.22, 0.22);
+const WALL_THICKNESS: f32 = 8.0;
+/// Distance from a door, in pixels, at which the player shows up when going through it.
+const DOOR_CLEARANCE: f32 = 36.0;
+const FADE_SECS: f32 = 0.4;
+
+fn corridor_bounds() -> WorldBounds {
+    WorldBounds {
+        min: -CORRIDOR_SIZE / 2.0,
+        max: CORRIDOR_SIZE / 2.0,
+    }
+}
+
+fn door_position(room: RoomId) -> Vec2 {
+    Vec2::new(
+        room.offset() * DOOR_SPACING,
+        CORRIDOR_SIZE.y / 2.0 - DOOR_SIZE.y / 2.0,
+    )
+}
+
+fn room_exit_position(room: RoomId) -> Vec2 {
+    room.center() - Vec2::new(0.0, ROOM_SIZE.y / 2.0 - DOOR_SIZE.y / 2.0)
+}
+
+/// Static colliders along the edges of an area of the given size, centered on its parent.
+fn walls(size: Vec2) -> impl Bundle {
+    let half_size = size / 2.0;
+
+    children![
+        (
+            RigidBody::Static,
+            Collider::rectangle(size.x, WALL_THICKNESS),
+            Transform::from_xyz(0.0, half_size.y, 0.0),
+        ),
+        (
+            RigidBody::Static,
+            Collider::rectangle(size.x, WALL_THICKNESS),
+            Transform::from_xyz(0.0, -half_size.y, 0.0),
+        ),
+        (
+            RigidBody::Static,
+            Collider::rectangle(WALL_THICKNESS, size.y),
+            Transform::from_xyz(half_size.x, 0.0, 0.0),
+        ),
+        (
+            RigidBody::Static,
+            Collider::rectangle(WALL_THICKNESS, size.y),
+            Transform::from_xyz(-half_size.x, 0.0, 0.0),
+        ),
+    ]
+}
+
+fn reset_room_record(mut room_record: ResMut<RoomRecord>) {
+    *room_record = RoomRecord::default();
+}
+
+fn spawn_corridor(mut commands: Commands, room_record: Res<RoomRecord>) {
+    commands.insert_resource(corridor_bounds());
+
+    commands.spawn((
+        DespawnOnExit(AppState::Exploring),
+        Sprite::from_color(CORRIDOR_COLOR, CORRIDOR_SIZE),
+        Transform::from_xyz(0.0, 0.0, -2.0),
+        walls(CORRIDOR_SIZE),
+    ));
+
+    for room in RoomId::all() {
+        let sealed = room_record.is_sealed(room);
+        let mut door = commands.spawn((
+            Door(room),
+            DespawnOnExit(AppState::Exploring),
+            Sprite::from_color(
+                if sealed {
+                    SEALED_DOOR_COLOR
+                } else {
+                    DOOR_COLOR
+                },
+                DOOR_SIZE,
+            ),
+            Transform::from_translation(door_position(room).extend(-1.0)),
+        ));
+
+        if !sealed {
+            door.insert(Interactable {
+                prompt: "prompt.open_door".to_string(),
+                radius: 24.0,
+            });
+        }
+    }
+}
+
+fn spawn_rooms(mut commands: Commands) {
+    for room in RoomId::all() {
+        commands.spawn((
+            DespawnOnExit(AppState::Exploring),
+            Sprite::from_color(ROOM_COLOR, ROOM_SIZE),
+            Transform::from_translation(room.center().extend(-2.0)),
+            walls(ROOM_SIZE),
+        ));
+
+        commands.spawn((
+            RoomExit(room),
+            DespawnOnExit(AppState::Exploring),
+            Sprite::from_color(DOOR_COLOR, DOOR_SIZE),
+            Transform::from_translation(room_exit_position(room).extend(-1.0)),
+            Interactable {
+                prompt: "prompt.leave_room".to_string(),
+                radius: 24.0,
+            },
+        ));
+    }
+}
+
+fn spawn_room_fade(mut commands: Commands) {
+    commands.spawn((
+        RoomFade,
+        DespawnOnExit(AppState::Exploring),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::NONE),
+        GlobalZIndex(1),
+    ));
+}
+
+fn start_room_transition(commands: &mut Commands, destination: RoomTransitionDestination) {
+    commands.insert_resource(RoomTransition {
+        destination,
+        timer: Timer::from_seconds(FADE_SECS, TimerMode::Once),
+        faded_out: false,
+    });
+    commands.run_system_cached(disable_context::<Player>);
+}
+
+fn open_door(
+    interacted: On<Interacted>,
+    mut commands: Commands,
+    doors: Query<&Door>,
+    room_record: Res<RoomRecord>,
+    room_transition: Option<Res<RoomTransition>>,
+) {
+    let Ok(Door(room)) = doors.get(interacted.target) else {
+        return;
+    };
+
+    if room_transition.is_none() && !room_record.is_visited(*room) {
+        commands.trigger(PlaySoundCue::new("door_creak"));
+        start_room_transition(&mut commands, RoomTransitionDestination::Room(*room));
+    }
+}
+
+fn leave_room(
+    interacted: On<Interacted>,
+    mut commands: Commands,
+    room_exits: Query<&RoomExit>,
+    room_transition: Option<Res<RoomTransition>>,
+) {
+    let Ok(RoomExit(room)) = room_exits.get(interacted.target) else {
+        return;
+    };
+
+    if room_transition.is_none() {
+        start_room_transition(&mut commands, RoomTransitionDestination::Corridor(*room));
+    }
+}
+
+fn advance_room_transition(
+    mut commands: Commands,
+    mut room_transition: ResMut<RoomTransition>,
+    room_fade: Single<&mut BackgroundColor, With<RoomFade>>,
+    player: Single<(&mut Transform, &mut LinearVelocity), With<Player>>,
+    main_camera: Single<&mut Transform, (With<MainCamera>, Without<Player>)>,
+    time: Res<Time>,
+) {
+    room_transition.timer.tick(time.delta());
+
+    let fraction = room_transition.timer.fraction();
+    let alpha = if room_transition.faded_out {
+        1.0 - fraction
+    } else {
+        fraction
+    };
+    room_fade.into_inner().0 = Color::BLACK.with_alpha(alpha);
+
+    if !room_transition.timer.is_finished() {
+        return;
+    }
+
+    if room_transition.faded_out {
+        commands.remove_resource::<RoomTransition>();
+        commands.run_system_cached(enable_context::<Player>);
+        return;
+    }
+
+    let (position, world_bounds) = match room_transition.destination {
+        RoomTransitionDestination::Room(room) => (
+            room_exit_position(room) + Vec2::Y * DOOR_CLEARANCE,
+            room.bounds(),
+        ),
+        RoomTransitionDestination::Corridor(room) => (
+            door_position(room) - Vec2::Y * DOOR_CLEARANCE,
+            corridor_bounds(),
+        ),
+    };
+
+    // Cut the camera straight to the player instead of letting it glide across the world.
+    let (mut player_transform, mut linear_velocity) = player.into_inner();
+    let mut camera_transform = main_camera.into_inner();
+    player_transform.translation = position.extend(player_transform.translation.z);
+    camera_transform.translation = position.extend(camera_transform.translation.z);
+    linear_velocity.0 = Vec2::ZERO;
+
+    commands.insert_resource(world_bounds);
+
+    match room_transition.destination {
+        RoomTransitionDestination::Room(room) => commands.trigger(RoomEntered(room)),
+        RoomTransitionDestination::Corridor(room) => commands.trigger(RoomExited(room)),
+    }
+
+    room_transition.faded_out = true;
+    room_transition.timer.reset();
+}
+
+fn clear_room_transition(mut commands: Commands, room_transition: Option<Res<RoomTransition>>) {
+    commands.remove_resource::<WorldBounds>();
+
+    if room_transition.is_some() {
+        commands.remove_resource::<RoomTransition>();
+        commands.run_system_cached(enable_context::<Player>);
+    }
+}
+
+fn on_room_entered(
+    entered: On<RoomEntered>,
+    mut commands: Commands,
+    mut room_record: ResMut<RoomRecord>,
+) {
+    room_record.visit(entered.0);
+    commands.trigger(RecordChoice(RunChoice::RoomEntered(entered.0)));
+}
+
+fn on_room_exited(
+    exited: On<RoomExited>,
+    mut commands: Commands,
+    mut room_record: ResMut<RoomRecord>,
+    mut doors: Query<(Entity, &Door, &mut Sprite)>,
+) {
+    room_record.seal(exited.0);
+
+    for (entity, Door(room), mut sprite) in &mut doors {
+        if *room == exited.0 {
+            sprite.color = SEALED_DOOR_COLOR;
+            commands.entity(entity).remove::<Interactable>();
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::BTreeSet;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::camera::WorldBounds;
+
+/// Number of doors along the corridor, each leading to its own room.
+pub const DOOR_COUNT: u8 = 5;
+
+/// Size of every room, in pixels.
+pub const ROOM_SIZE: Vec2 = Vec2::new(320.0, 200.0);
+
+/// One of the rooms behind the corridor doors, numbered from left to right.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct RoomId(pub u8);
+
+impl RoomId {
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..DOOR_COUNT).map(Self)
+    }
+
+    /// Returns the center of the room, in pixels. Rooms are laid out in a row above the corridor,
+    /// far enough apart that they never show up on screen together.
+    pub fn center(&self) -> Vec2 {
+        const ROOM_SPACING: f32 = 640.0;
+        const ROOM_ROW_Y: f32 = 800.0;
+
+        Vec2::new(self.offset() * ROOM_SPACING, ROOM_ROW_Y)
+    }
+
+    /// Returns how many slots away from the middle door this room's door is, negative to the left.
+    pub(super) fn offset(&self) -> f32 {
+        f32::from(self.0) - f32::from(DOOR_COUNT - 1) / 2.0
+    }
+
+    pub fn bounds(&self) -> WorldBounds {
+        let center = self.center();
+
+        WorldBounds {
+            min: center - ROOM_SIZE / 2.0,
+            max: center + ROOM_SIZE / 2.0,
+        }
+    }
+}
+
+/// Which rooms the player has been into during the current run. Rooms are one-way: once the player
+/// walks out of one it's sealed and its door never opens again.
+#[derive(Resource, Default, Debug)]
+pub struct RoomRecord {
+    visited: BTreeSet<RoomId>,
+    sealed: BTreeSet<RoomId>,
+}
+
+impl RoomRecord {
+    pub fn visit(&mut self, room: RoomId) {
+        self.visited.insert(room);
+    }
+
+    pub fn seal(&mut self, room: RoomId) {
+        self.sealed.insert(room);
+    }
+
+    pub fn is_visited(&self, room: RoomId) -> bool {
+        self.visited.contains(&room)
+    }
+
+    pub fn is_sealed(&self, room: RoomId) -> bool {
+        self.sealed.contains(&room)
+    }
+}
+
+/// Triggered once the player is inside a room and the screen starts fading back in.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct RoomEntered(pub RoomId);
+
+/// Triggered once the player is back in the corridor after leaving a room.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct RoomExited(pub RoomId);
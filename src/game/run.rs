@@ -0,0 +1,43 @@
+use std::collections::BTreeSet;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::state::AppState;
+
+pub struct RunStatePlugin;
+
+impl Plugin for RunStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunState>();
+
+        app.add_systems(OnEnter(AppState::Falling), reset_run_state);
+    }
+}
+
+/// Outcomes of the current run that change what the rest of the game offers.
+#[derive(Resource, Default, Debug)]
+pub struct RunState {
+    flags: BTreeSet<RunFlag>,
+}
+
+impl RunState {
+    pub fn set(&mut self, flag: RunFlag) {
+        self.flags.insert(flag);
+    }
+
+    pub fn is_set(&self, flag: RunFlag) -> bool {
+        self.flags.contains(&flag)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Deserialize)]
+pub enum RunFlag {
+    KnotUntied,
+    GoldenBridgeOpened,
+    GoldenBridgeCrossed,
+}
+
+fn reset_run_state(mut run_state: ResMut<RunState>) {
+    *run_state = RunState::default();
+}
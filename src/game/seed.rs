@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::state::AppState;
+
+pub struct RunSeedPlugin;
+
+impl Plugin for RunSeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunSeed>();
+
+        app.add_systems(OnEnter(AppState::Falling), reset_run_seed);
+    }
+}
+
+/// Seed for everything randomized during a run, derived from the binary choices the player makes
+/// while falling.
+///
+/// Consumers should draw from their own named stream through [`RunSeed::fork`] rather than share
+/// one generator, so adding a new consumer never changes what existing ones get.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RunSeed {
+    choices: Vec<bool>,
+}
+
+impl RunSeed {
+    pub fn from_choices(choices: impl IntoIterator<Item = bool>) -> Self {
+        Self {
+            choices: choices.into_iter().collect(),
+        }
+    }
+
+    /// Appends a choice to the ones this seed is built from.
+    pub fn push_choice(&mut self, choice: bool) {
+        self.choices.push(choice);
+    }
+
+    pub fn choices(&self) -> &[bool] {
+        &self.choices
+    }
+
+    /// Returns the numeric seed. Each choice is mixed in order, along with its position, so
+    /// `[true]` and `[true, false]` produce unrelated seeds.
+    pub fn value(&self) -> u64 {
+        self.choices
+            .iter()
+            .enumerate()
+            .fold(SEED_BASIS, |seed, (index, choice)| {
+                split_mix(seed ^ (((index as u64) << 1) | u64::from(*choice)))
+            })
+    }
+
+    /// Returns a generator for the named stream, such as `"doors"` or `"poem"`.
+    pub fn fork(&self, stream: &str) -> ChaCha8Rng {
+        self.fork_indexed(stream, 0)
+    }
+
+    /// Returns a generator for one of many instances of the named stream, such as the objects of
+    /// a given room.
+    pub fn fork_indexed(&self, stream: &str, index: u64) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.value());
+        rng.set_stream(split_mix(fnv1a(stream.as_bytes()) ^ index));
+        rng
+    }
+}
+
+const SEED_BASIS: u64 = 0x6a09_e667_f3bc_c908;
+
+/// Hashes `bytes` with FNV-1a, which unlike the standard library's hasher is guaranteed to give the
+/// same result on every platform and toolchain.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Scrambles `value` with the SplitMix64 finalizer so nearby inputs produce unrelated outputs.
+fn split_mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn reset_run_seed(mut run_seed: ResMut<RunSeed>) {
+    *run_seed = RunSeed::default();
+}
@@ -0,0 +1,211 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::physics::SpeedMultiplier;
+
+pub struct SlowdownPlugin;
+
+impl Plugin for SlowdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_slowdown_fields);
+    }
+}
+
+/// Slows down every entity with a [`Speed`](crate::physics::Speed) that comes within `radius`
+/// pixels of this entity. The closer the mover gets, the slower it goes.
+///
+/// Overlapping fields multiply their slowdowns together.
+#[derive(Component, Deserialize, Clone, Debug)]
+pub struct SlowdownField {
+    /// Distance, in pixels, at which the field starts to take effect.
+    pub radius: f32,
+    /// Fraction of speed taken away at the center of the field, from `0.0` to `1.0`.
+    pub strength: f32,
+    #[serde(default)]
+    pub falloff: SlowdownFalloff,
+    /// Lowest speed multiplier this field lets a mover reach, so it never gets stuck for good. When
+    /// fields overlap, the highest floor among them wins.
+    #[serde(default)]
+    pub floor: Option<f32>,
+}
+
+/// Shape of a [`SlowdownField`]'s effect, going from its edge to its center.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub enum SlowdownFalloff {
+    #[default]
+    Linear,
+    SmoothStep,
+    /// Evenly spaced samples from the edge to the center, linearly interpolated. Each sample is
+    /// the fraction of the field's strength applied at that point, and is kept between `0.0` and
+    /// `1.0` so a field never speeds movers up or turns them around.
+    Samples(Vec<f32>),
+}
+
+impl SlowdownFalloff {
+    /// Returns how much of the field's strength applies at `t`, where `0.0` is the edge of the
+    /// field and `1.0` its center.
+    pub fn sample(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Self::Linear => t,
+            Self::SmoothStep => t * t * (3.0 - 2.0 * t),
+            Self::Samples(samples) => match samples.as_slice() {
+                [] => t,
+                [sample] => sample.clamp(0.0, 1.0),
+                samples => {
+                    let position = t * (samples.len() - 1) as f32;
+                    let index = (position.floor() as usize).min(samples.len() - 2);
+                    let fraction = position - index as f32;
+                    (samples[index] + (samples[index + 1] - samples[index]) * fraction)
+                        .clamp(0.0, 1.0)
+                }
+            },
+        }
+    }
+}
+
+impl SlowdownField {
+    /// Returns the speed multiplier this field applies to a mover `distance` pixels away.
+    pub fn multiplier_at(&self, distance: f32) -> f32 {
+        if distance >= self.radius {
+            return 1.0;
+        }
+
+        let t = 1.0 - distance / self.radius;
+        1.0 - self.strength.clamp(0.0, 1.0) * self.falloff.sample(t)
+    }
+}
+
+/// Returns the speed multiplier of a mover within reach of `fields`, each given with the mover's
+/// distance to it.
+fn combined_multiplier<'a>(fields: impl IntoIterator<Item = (&'a SlowdownField, f32)>) -> f32 {
+    let mut multiplier = 1.0_f32;
+    let mut floor = None::<f32>;
+
+    for (field, distance) in fields {
+        if distance < field.radius {
+            multiplier *= field.multiplier_at(distance);
+
+            if let Some(field_floor) = field.floor {
+                floor = Some(floor.map_or(field_floor, |floor| floor.max(field_floor)));
+            }
+        }
+    }
+
+    match floor {
+        Some(floor) => multiplier.max(floor),
+        None => multiplier,
+    }
+}
+
+fn apply_slowdown_fields(
+    mut movers: Query<(&GlobalTransform, &mut SpeedMultiplier)>,
+    fields: Query<(&GlobalTransform, &SlowdownField)>,
+) {
+    for (mover_transform, mut speed_multiplier) in &mut movers {
+        let mover_position = mover_transform.translation().xy();
+        let multiplier = combined_multiplier(fields.iter().map(|(field_transform, field)| {
+            (
+                field,
+                field_transform.translation().xy().distance(mover_position),
+            )
+        }));
+
+        speed_multiplier.set_if_neq(SpeedMultiplier(multiplier));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(strength: f32, falloff: SlowdownFalloff, floor: Option<f32>) -> SlowdownField {
+        SlowdownField {
+            radius: 100.0,
+            strength,
+            falloff,
+            floor,
+        }
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn falloffs_go_from_nothing_at_the_edge_to_everything_at_the_center() {
+        for falloff in [
+            SlowdownFalloff::Linear,
+            SlowdownFalloff::SmoothStep,
+            SlowdownFalloff::Samples(vec![0.0, 0.8, 1.0]),
+        ] {
+            assert_close(falloff.sample(0.0), 0.0);
+            assert_close(falloff.sample(1.0), 1.0);
+            // Out of range positions count as the edge or the center.
+            assert_close(falloff.sample(-1.0), 0.0);
+            assert_close(falloff.sample(2.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn falloffs_shape_the_middle_of_the_field() {
+        assert_close(SlowdownFalloff::Linear.sample(0.25), 0.25);
+        assert_close(SlowdownFalloff::SmoothStep.sample(0.25), 0.15625);
+        assert_close(SlowdownFalloff::SmoothStep.sample(0.5), 0.5);
+        assert_close(
+            SlowdownFalloff::Samples(vec![0.0, 0.8, 1.0]).sample(0.25),
+            0.4,
+        );
+        assert_close(SlowdownFalloff::Samples(vec![0.3]).sample(0.9), 0.3);
+        assert_close(SlowdownFalloff::Samples(Vec::new()).sample(0.6), 0.6);
+    }
+
+    #[test]
+    fn out_of_range_samples_are_clamped() {
+        let falloff = SlowdownFalloff::Samples(vec![-0.5, 1.5]);
+
+        assert_close(falloff.sample(0.0), 0.0);
+        assert_close(falloff.sample(0.5), 0.5);
+        assert_close(falloff.sample(1.0), 1.0);
+        assert_close(SlowdownFalloff::Samples(vec![2.0]).sample(0.5), 1.0);
+    }
+
+    #[test]
+    fn multiplier_follows_distance_and_strength() {
+        let field = field(0.5, SlowdownFalloff::Linear, None);
+
+        assert_close(field.multiplier_at(0.0), 0.5);
+        assert_close(field.multiplier_at(50.0), 0.75);
+        assert_close(field.multiplier_at(100.0), 1.0);
+        assert_close(field.multiplier_at(150.0), 1.0);
+    }
+
+    #[test]
+    fn floor_keeps_movers_from_stopping() {
+        let field = field(1.0, SlowdownFalloff::Linear, Some(0.2));
+
+        assert_close(combined_multiplier([(&field, 0.0)]), 0.2);
+        assert_close(combined_multiplier([(&field, 50.0)]), 0.5);
+        // Out of reach, the floor doesn't apply either.
+        assert_close(combined_multiplier([(&field, 120.0)]), 1.0);
+    }
+
+    #[test]
+    fn overlapping_fields_multiply_and_keep_the_highest_floor() {
+        let near = field(0.5, SlowdownFalloff::Linear, Some(0.1));
+        let far = field(1.0, SlowdownFalloff::Linear, Some(0.3));
+
+        assert_close(combined_multiplier([(&near, 0.0), (&far, 50.0)]), 0.3);
+        assert_close(
+            combined_multiplier([
+                (&near, 50.0),
+                (&field(0.5, SlowdownFalloff::Linear, None), 0.0),
+            ]),
+            0.375,
+        );
+    }
+}
@@ -83,9 +83,12 @@ impl Cursor<'_, '_> {
 fn bind_ui_actions(mut commands: Commands) {
     use ui::*;
 
+    // Screens with buttons to pick from enable it while they're shown, so menu input doesn't
+    // leak into gameplay.
     commands.spawn((
         UiContext,
         ContextPriority::<UiContext>::new(0),
+        ContextActivity::<UiContext>::INACTIVE,
         Actions::<UiContext>::spawn((
             Spawn((
                 Action::<Back>::new(),
@@ -113,7 +116,10 @@ fn bind_ui_actions(mut commands: Commands) {
                     ),
                 )),
             )),
-            Spawn((Action::<Select>::new(), bindings![GamepadButton::South])),
+            Spawn((
+                Action::<Select>::new(),
+                bindings![GamepadButton::South, KeyCode::Enter],
+            )),
             Spawn((
                 Action::<Pause>::new(),
                 ActionSettings {
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
+use serde::Deserialize;
+
+use crate::{
+    assets::{
+        AssetCategory, AssetCollection, AssetCollectionAppExt, AssetManifest, RonAssetPlugin,
+    },
+    state::AppState,
+};
+
+const WINDOW_TITLE_KEY: &str = "window.title";
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<LocaleStrings>::new(&["locale.ron"]));
+        app.add_asset_collection::<Locales>();
+
+        app.init_resource::<Locale>();
+
+        app.add_systems(OnExit(AppState::Loading), update_window_title);
+        app.add_systems(
+            Update,
+            (
+                update_window_title.run_if(resource_changed::<Locale>),
+                update_localized_texts,
+            )
+                .run_if(not(in_state(AppState::Loading))),
+        );
+    }
+}
+
+/// Language the game's text is shown in. Can be changed at any time, and every
+/// [`LocalizedText`] follows.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Locale {
+    /// Returns the locale after this one, wrapping around, for settings that cycle through them.
+    pub fn next(self) -> Self {
+        match self {
+            Self::English => Self::Spanish,
+            Self::Spanish => Self::English,
+        }
+    }
+
+    /// Name of this locale's strings in the asset manifest.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Spanish => "es",
+        }
+    }
+}
+
+/// Translated strings of a single locale, keyed by the same names in every locale.
+#[derive(Asset, TypePath, Deserialize, Default, Deref)]
+#[serde(transparent)]
+pub struct LocaleStrings(BTreeMap<String, String>);
+
+pub struct Locales;
+
+impl AssetCategory for Locales {
+    type Asset = LocaleStrings;
+
+    const NAME: &'static str = "locales";
+
+    fn entries(manifest: &AssetManifest) -> &BTreeMap<String, String> {
+        &manifest.locales
+    }
+}
+
+pub type LocaleHandles = AssetCollection<Locales>;
+
+/// Looks up strings in the current [`Locale`].
+#[derive(SystemParam)]
+pub struct Localization<'w> {
+    locale: Res<'w, Locale>,
+    locale_handles: Res<'w, LocaleHandles>,
+    locale_strings: Res<'w, Assets<LocaleStrings>>,
+}
+
+impl Localization<'_> {
+    /// Returns the text for `key`, falling back to English when the current locale lacks it, and
+    /// to the key itself when no locale has it so missing strings stand out.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        [*self.locale, Locale::English]
+            .into_iter()
+            .find_map(|locale| {
+                let handle = self.locale_handles.get(locale.code())?;
+                self.locale_strings.get(&handle)?.get(key)
+            })
+            .map_or(key, String::as_str)
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.locale.is_changed()
+    }
+}
+
+/// Sets the [`Text`] of this entity to the string with this key, and keeps it up to date when the
+/// [`Locale`] changes.
+#[derive(Component, Clone, Debug)]
+#[require(Text)]
+pub struct LocalizedText(pub String);
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+}
+
+fn update_localized_texts(
+    mut localized_texts: Query<(Ref<LocalizedText>, &mut Text)>,
+    mut locale_strings_events: MessageReader<AssetEvent<LocaleStrings>>,
+    localization: Localization,
+) {
+    // Strings may also change under a running game when their files are hot-reloaded.
+    let refresh_all = localization.is_changed() || locale_strings_events.read().count() > 0;
+
+    for (localized_text, mut text) in &mut localized_texts {
+        if !refresh_all && !localized_text.is_changed() {
+            continue;
+        }
+
+        let localized = localization.get(&localized_text.0);
+
+        if text.0 != localized {
+            text.0 = localized.to_string();
+        }
+    }
+}
+
+fn update_window_title(
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+    localization: Localization,
+) {
+    let title = localization.get(WINDOW_TITLE_KEY);
+
+    if window.title != title {
+        window.title = title.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, fs, path::Path};
+
+    use super::*;
+
+    fn read_asset(path: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("assets")
+            .join(path);
+        fs::read_to_string(&path).unwrap_or_else(|error| panic!("{}: {error}", path.display()))
+    }
+
+    #[test]
+    fn every_locale_has_the_same_keys() {
+        let manifest = ron::from_str::<AssetManifest>(&read_asset("manifest.ron")).unwrap();
+        let keys = [Locale::English, Locale::Spanish].map(|locale| {
+            let path = manifest
+                .locales
+                .get(locale.code())
+                .unwrap_or_else(|| panic!("{locale:?} is missing from the asset manifest"));
+            let strings = ron::from_str::<LocaleStrings>(&read_asset(path)).unwrap();
+
+            (
+                locale,
+                strings.keys().cloned().collect::<BTreeSet<String>>(),
+            )
+        });
+        let all_keys = keys
+            .iter()
+            .flat_map(|(_, keys)| keys.iter().cloned())
+            .collect::<BTreeSet<String>>();
+
+        let missing = keys
+            .iter()
+            .filter_map(|(locale, keys)| {
+                let missing = all_keys.difference(keys).cloned().collect::<Vec<String>>();
+                (!missing.is_empty()).then(|| format!("{locale:?} is missing {missing:?}"))
+            })
+            .collect::<Vec<String>>();
+
+        assert!(missing.is_empty(), "{}", missing.join("\n"));
+    }
+}
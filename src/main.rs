@@ -1,14 +1,17 @@
 use bevy::prelude::*;
 
 mod animation;
+mod assets;
 mod audio;
 mod camera;
 mod fonts;
 mod game;
 mod game_timer;
 mod input;
+mod localization;
 mod pause;
 mod physics;
+mod state;
 mod textures;
 mod ui;
 
@@ -20,6 +23,7 @@ fn main() {
             .set(ImagePlugin::default_nearest())
             .set(WindowPlugin {
                 primary_window: Some(Window {
+                    // Replaced by the localized title once the locales are loaded.
                     title: "Bevy Jam 7".to_string(),
                     ..Default::default()
                 }),
@@ -28,16 +32,19 @@ fn main() {
     );
 
     app.add_plugins((
+        assets::AssetsPlugin,
         camera::CameraPlugin,
         input::InputPlugin,
         textures::TexturesPlugin,
         audio::AudioPlugin,
         fonts::FontsPlugin,
+        localization::LocalizationPlugin,
         ui::UiPlugin,
         physics::PhysicsPlugin,
         animation::SpriteAnimationPlugin,
         pause::PausePlugin,
         game::GamePlugin,
+        state::StatePlugin,
     ));
 
     app.insert_resource(ClearColor(Color::BLACK));
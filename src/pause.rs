@@ -1,10 +1,28 @@
 use bevy::prelude::*;
+use bevy_enhanced_input::prelude::{ContextActivity, Start};
+
+use crate::{
+    audio::MusicDucked,
+    game::player::Player,
+    input::{
+        actions::ui::{self, UiContext},
+        disable_context, enable_context,
+    },
+    state::InGame,
+};
 
 pub struct PausePlugin;
 
 impl Plugin for PausePlugin {
     fn build(&self, app: &mut App) {
+        app.add_sub_state::<Pause>();
+        app.add_computed_state::<Paused>();
+
+        app.add_systems(OnEnter(Paused), pause_game);
+        app.add_systems(OnExit(Paused), resume_game);
+
         app.add_observer(on_pause_event);
+        app.add_observer(toggle_pause);
     }
 }
 
@@ -14,6 +32,40 @@ pub enum PauseEvent {
     Resume,
 }
 
+/// Whether a run is paused and, if so, which screen of the pause menu is open.
+#[derive(SubStates, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(InGame = InGame)]
+pub enum Pause {
+    #[default]
+    Running,
+    Menu,
+    Settings,
+}
+
+/// Active while any screen of the pause menu is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Paused;
+
+impl ComputedStates for Paused {
+    type SourceStates = Pause;
+
+    fn compute(sources: Pause) -> Option<Self> {
+        match sources {
+            Pause::Running => None,
+            Pause::Menu | Pause::Settings => Some(Self),
+        }
+    }
+}
+
+/// Marks that pausing suspended the player context, so resuming should give it back.
+#[derive(Resource)]
+struct PlayerContextSuspended;
+
+/// Marks that pausing enabled the UI context, so resuming should disable it again. Dialogues and
+/// falling prompts keep it enabled on their own.
+#[derive(Resource)]
+struct UiContextLent;
+
 fn on_pause_event(pause: On<PauseEvent>, mut time: ResMut<Time<Virtual>>) {
     match pause.event() {
         PauseEvent::Pause => {
@@ -24,3 +76,71 @@ fn on_pause_event(pause: On<PauseEvent>, mut time: ResMut<Time<Virtual>>) {
         }
     }
 }
+
+fn toggle_pause(
+    _: On<Start<ui::Pause>>,
+    pause: Option<Res<State<Pause>>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+) {
+    match pause.as_deref().map(State::get) {
+        Some(Pause::Running) => next_pause.set(Pause::Menu),
+        Some(Pause::Menu) => next_pause.set(Pause::Running),
+        // Escape also triggers `Back`, which leaves the settings for the pause menu.
+        Some(Pause::Settings) | None => {}
+    }
+}
+
+fn pause_game(
+    mut commands: Commands,
+    player_contexts: Query<&ContextActivity<Player>>,
+    ui_contexts: Query<&ContextActivity<UiContext>>,
+    mut music_ducked: ResMut<MusicDucked>,
+) {
+    commands.trigger(PauseEvent::Pause);
+
+    // Dialogues and the knot puzzle suspend the player context on their own, and expect to be the
+    // ones giving it back.
+    if player_contexts
+        .iter()
+        .any(|context_activity| **context_activity)
+    {
+        commands.insert_resource(PlayerContextSuspended);
+        commands.run_system_cached(disable_context::<Player>);
+    }
+
+    if ui_contexts
+        .iter()
+        .any(|context_activity| !**context_activity)
+    {
+        commands.insert_resource(UiContextLent);
+        commands.run_system_cached(enable_context::<UiContext>);
+    }
+
+    music_ducked.0 = true;
+}
+
+fn resume_game(
+    mut commands: Commands,
+    player_context_suspended: Option<Res<PlayerContextSuspended>>,
+    ui_context_lent: Option<Res<UiContextLent>>,
+    players: Query<(), With<Player>>,
+    mut music_ducked: ResMut<MusicDucked>,
+) {
+    commands.trigger(PauseEvent::Resume);
+
+    if player_context_suspended.is_some() {
+        commands.remove_resource::<PlayerContextSuspended>();
+
+        // The run may be ending instead, in which case the player is going away with it.
+        if !players.is_empty() {
+            commands.run_system_cached(enable_context::<Player>);
+        }
+    }
+
+    if ui_context_lent.is_some() {
+        commands.remove_resource::<UiContextLent>();
+        commands.run_system_cached(disable_context::<UiContext>);
+    }
+
+    music_ducked.0 = false;
+}
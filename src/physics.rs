@@ -5,7 +5,7 @@ use avian2d::{
 use bevy::prelude::*;
 
 /// Represents the pixels-per-meter unit for the physics engine.
-const LENGTH_UNIT: f32 = 32.0;
+pub const LENGTH_UNIT: f32 = 32.0;
 
 pub struct PhysicsPlugin;
 
@@ -14,15 +14,34 @@ impl Plugin for PhysicsPlugin {
         app.add_plugins(PhysicsPlugins::default().with_length_unit(LENGTH_UNIT));
 
         #[cfg(debug_assertions)]
-        app.add_plugins(avian2d::prelude::PhysicsDebugPlugin);
+        app.add_plugins(debug::DebugGizmosPlugin);
 
         app.insert_resource(Gravity::ZERO);
     }
 }
 
 #[derive(Component, Deref, DerefMut)]
+#[require(SpeedMultiplier)]
 pub struct Speed(pub f32);
 
+impl Speed {
+    /// Returns this speed scaled by the entity's current [`SpeedMultiplier`].
+    pub fn effective(&self, multiplier: &SpeedMultiplier) -> f32 {
+        self.0 * multiplier.0
+    }
+}
+
+/// Scales an entity's [`Speed`]. Systems that slow entities down or speed them up should write to
+/// this instead of changing the base speed.
+#[derive(Component, Deref, DerefMut, PartialEq)]
+pub struct SpeedMultiplier(pub f32);
+
+impl Default for SpeedMultiplier {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
 #[derive(PhysicsLayer, Default)]
 pub enum CollisionLayer {
     #[default]
@@ -30,3 +49,52 @@ pub enum CollisionLayer {
     Player,
     Interactable,
 }
+
+/// Physics debug gizmos, shown in debug builds and toggled with F3.
+#[cfg(debug_assertions)]
+mod debug {
+    use avian2d::prelude::PhysicsGizmos;
+    use bevy::prelude::*;
+    use bevy_enhanced_input::prelude::*;
+
+    pub struct DebugGizmosPlugin;
+
+    impl Plugin for DebugGizmosPlugin {
+        fn build(&self, app: &mut App) {
+            app.add_plugins(avian2d::prelude::PhysicsDebugPlugin);
+
+            app.add_input_context::<DebugContext>();
+
+            app.add_systems(Startup, bind_debug_actions);
+
+            app.add_observer(toggle_debug_gizmos);
+        }
+    }
+
+    /// Input context for debugging tools, always active in debug builds.
+    #[derive(Component, Default)]
+    struct DebugContext;
+
+    /// Shows or hides the physics debug gizmos, and everything else drawn along with them.
+    #[derive(InputAction)]
+    #[action_output(bool)]
+    struct ToggleDebugGizmos;
+
+    fn bind_debug_actions(mut commands: Commands) {
+        commands.spawn((
+            DebugContext,
+            Actions::<DebugContext>::spawn(Spawn((
+                Action::<ToggleDebugGizmos>::new(),
+                bindings![KeyCode::F3],
+            ))),
+        ));
+    }
+
+    fn toggle_debug_gizmos(
+        _: On<Start<ToggleDebugGizmos>>,
+        mut config_store: ResMut<GizmoConfigStore>,
+    ) {
+        let (config, _) = config_store.config_mut::<PhysicsGizmos>();
+        config.enabled = !config.enabled;
+    }
+}
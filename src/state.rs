@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::assets::AssetsLoadProgress;
+
+pub struct StatePlugin;
+
+impl Plugin for StatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<AppState>();
+        app.add_computed_state::<InGame>();
+
+        app.add_systems(
+            Update,
+            finish_loading.run_if(in_state(AppState::Loading).and(AssetsLoadProgress::loaded)),
+        );
+    }
+}
+
+/// Top-level state machine. A run goes through `Falling`, `Exploring` and `Ending`, and going back
+/// to `MainMenu` tears it down so a new one can be started without relaunching the game.
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AppState {
+    #[default]
+    Loading,
+    MainMenu,
+    Falling,
+    Exploring,
+    Ending,
+}
+
+/// Active while a run is in progress. Entities that must survive between run phases, such as the
+/// player, should be scoped to this state rather than to a single [`AppState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InGame;
+
+impl ComputedStates for InGame {
+    type SourceStates = AppState;
+
+    fn compute(sources: AppState) -> Option<Self> {
+        match sources {
+            AppState::Falling | AppState::Exploring | AppState::Ending => Some(Self),
+            AppState::Loading | AppState::MainMenu => None,
+        }
+    }
+}
+
+fn finish_loading(mut next_state: ResMut<NextState<AppState>>) {
+    next_state.set(AppState::MainMenu);
+}
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+use crate::{
+    assets::AssetsLoadProgress, audio::AudioLoadStates, fonts::FontsLoadState, state::AppState,
+    textures::TexturesLoadState,
+};
+
+pub struct LoadingScreenPlugin;
+
+impl Plugin for LoadingScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Loading), spawn_loading_screen);
+        app.add_systems(
+            Update,
+            (update_loading_progress_bars, update_loading_failed_list)
+                .run_if(in_state(AppState::Loading)),
+        );
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+enum LoadingCategory {
+    Overall,
+    Textures,
+    Fonts,
+    Music,
+    SoundEffects,
+}
+
+impl LoadingCategory {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Overall => "Overall",
+            Self::Textures => "Textures",
+            Self::Fonts => "Fonts",
+            Self::Music => "Music",
+            Self::SoundEffects => "Sound effects",
+        }
+    }
+}
+
+#[derive(Component)]
+struct LoadingFailedList;
+
+const PROGRESS_BAR_COLOR: Color = Color::WHITE;
+const FAILED_TEXT_COLOR: Color = Color::srgb(0.9, 0.3, 0.3);
+
+fn spawn_loading_screen(mut commands: Commands) {
+    commands.spawn((
+        DespawnOnExit(AppState::Loading),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        children![
+            (Text::new("Loading"), TextFont::from_font_size(32.0)),
+            progress_bar(LoadingCategory::Overall),
+            progress_bar(LoadingCategory::Textures),
+            progress_bar(LoadingCategory::Fonts),
+            progress_bar(LoadingCategory::Music),
+            progress_bar(LoadingCategory::SoundEffects),
+            (
+                LoadingFailedList,
+                Text::default(),
+                TextFont::from_font_size(14.0),
+                TextColor(FAILED_TEXT_COLOR),
+            ),
+        ],
+    ));
+}
+
+fn progress_bar(category: LoadingCategory) -> impl Bundle {
+    (
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(12.0),
+            ..default()
+        },
+        children![
+            (
+                Text::new(category.label()),
+                TextFont::from_font_size(16.0),
+                Node {
+                    width: Val::Px(128.0),
+                    ..default()
+                },
+            ),
+            (
+                Node {
+                    width: Val::Px(240.0),
+                    height: Val::Px(12.0),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor::all(PROGRESS_BAR_COLOR),
+                children![(
+                    category,
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(PROGRESS_BAR_COLOR),
+                )],
+            ),
+        ],
+    )
+}
+
+fn update_loading_progress_bars(
+    mut progress_bars: Query<(&LoadingCategory, &mut Node)>,
+    load_progress: Res<AssetsLoadProgress>,
+    textures_load_state: Res<TexturesLoadState>,
+    fonts_load_state: Res<FontsLoadState>,
+    audio_load_states: AudioLoadStates,
+) {
+    for (category, mut node) in &mut progress_bars {
+        let progress = match category {
+            LoadingCategory::Overall => load_progress.fraction(),
+            LoadingCategory::Textures => textures_load_state.progress(),
+            LoadingCategory::Fonts => fonts_load_state.progress(),
+            LoadingCategory::Music => audio_load_states.music_load_state.progress(),
+            LoadingCategory::SoundEffects => audio_load_states.sound_effects_load_state.progress(),
+        };
+        node.width = Val::Percent(progress * 100.0);
+    }
+}
+
+fn update_loading_failed_list(
+    failed_list: Single<&mut Text, With<LoadingFailedList>>,
+    textures_load_state: Res<TexturesLoadState>,
+    fonts_load_state: Res<FontsLoadState>,
+    audio_load_states: AudioLoadStates,
+) {
+    let failed = textures_load_state
+        .failures()
+        .iter()
+        .chain(fonts_load_state.failures())
+        .chain(audio_load_states.music_load_state.failures())
+        .chain(audio_load_states.sound_effects_load_state.failures())
+        .map(|failure| format!("{} ({}): {}", failure.name, failure.path, failure.error))
+        .collect::<Vec<String>>();
+
+    let mut text = failed_list.into_inner();
+
+    if failed.is_empty() {
+        text.0.clear();
+    } else {
+        text.0 = format!("Failed to load:\n{}", failed.join("\n"));
+    }
+}
@@ -0,0 +1,174 @@
+use bevy::{prelude::*, ui_widgets::Activate};
+use bevy_enhanced_input::prelude::Start;
+
+use crate::{
+    audio::AudioMixer,
+    input::{
+        actions::ui::{Back, UiContext},
+        disable_context, enable_context,
+    },
+    localization::LocalizedText,
+    state::AppState,
+};
+
+use super::{navigation::FirstNavigableNode, settings::settings_panel, widgets::text_button};
+
+pub struct MainMenuPlugin;
+
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_sub_state::<MenuScreen>();
+
+        app.add_systems(OnEnter(AppState::MainMenu), enable_context::<UiContext>);
+        app.add_systems(OnExit(AppState::MainMenu), disable_context::<UiContext>);
+        app.add_systems(OnEnter(MenuScreen::Main), spawn_main_screen);
+        app.add_systems(OnEnter(MenuScreen::Settings), spawn_settings_screen);
+        app.add_systems(OnEnter(MenuScreen::Credits), spawn_credits_screen);
+
+        app.add_observer(on_activate_menu_button);
+        app.add_observer(go_back);
+    }
+}
+
+/// Screen of the main menu being shown.
+#[derive(SubStates, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(AppState = AppState::MainMenu)]
+enum MenuScreen {
+    #[default]
+    Main,
+    Settings,
+    Credits,
+}
+
+impl MenuScreen {
+    /// Screen that `Back` returns to, if any.
+    fn previous(self) -> Option<Self> {
+        match self {
+            Self::Main => None,
+            Self::Settings | Self::Credits => Some(Self::Main),
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+enum MenuButton {
+    Start,
+    Settings,
+    Credits,
+    #[cfg(not(target_family = "wasm"))]
+    Quit,
+    Back,
+}
+
+fn spawn_main_screen(mut commands: Commands) {
+    commands
+        .spawn(menu_screen(MenuScreen::Main, "window.title"))
+        .with_children(|parent| {
+            parent.spawn((
+                menu_button(MenuButton::Start, "menu.start"),
+                FirstNavigableNode,
+            ));
+            parent.spawn(menu_button(MenuButton::Settings, "menu.settings"));
+            parent.spawn(menu_button(MenuButton::Credits, "menu.credits"));
+            // Browsers don't let pages close their own tab, so there's nothing to quit to.
+            #[cfg(not(target_family = "wasm"))]
+            parent.spawn(menu_button(MenuButton::Quit, "menu.quit"));
+        });
+}
+
+fn spawn_settings_screen(mut commands: Commands, mixer: Res<AudioMixer>) {
+    commands
+        .spawn(menu_screen(MenuScreen::Settings, "settings.title"))
+        .with_children(|parent| {
+            parent.spawn(settings_panel(&mixer));
+            parent.spawn(menu_button(MenuButton::Back, "menu.back"));
+        });
+}
+
+fn spawn_credits_screen(mut commands: Commands) {
+    commands
+        .spawn(menu_screen(MenuScreen::Credits, "credits.title"))
+        .with_children(|parent| {
+            parent.spawn((
+                LocalizedText::new("credits.body"),
+                TextFont::from_font_size(16.0),
+                TextLayout::new_with_justify(Justify::Center),
+            ));
+            parent.spawn((
+                menu_button(MenuButton::Back, "menu.back"),
+                FirstNavigableNode,
+            ));
+        });
+}
+
+/// Full-screen column holding a menu screen, despawned when leaving it.
+fn menu_screen(screen: MenuScreen, title_key: &str) -> impl Bundle {
+    (
+        DespawnOnExit(screen),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        children![(
+            LocalizedText::new(title_key),
+            TextFont::from_font_size(32.0),
+            Node {
+                margin: UiRect::bottom(Val::Px(24.0)),
+                ..default()
+            },
+        )],
+    )
+}
+
+fn menu_button(button: MenuButton, label_key: &str) -> impl Bundle {
+    (button, text_button(label_key))
+}
+
+fn on_activate_menu_button(
+    activate: On<Activate>,
+    menu_buttons: Query<&MenuButton>,
+    menu_screen: Option<Res<State<MenuScreen>>>,
+    mut next_menu_screen: ResMut<NextState<MenuScreen>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    #[cfg(not(target_family = "wasm"))] mut app_exit: MessageWriter<AppExit>,
+) {
+    let Ok(button) = menu_buttons.get(activate.entity) else {
+        return;
+    };
+
+    match button {
+        MenuButton::Start => next_app_state.set(AppState::Falling),
+        MenuButton::Settings => next_menu_screen.set(MenuScreen::Settings),
+        MenuButton::Credits => next_menu_screen.set(MenuScreen::Credits),
+        #[cfg(not(target_family = "wasm"))]
+        MenuButton::Quit => {
+            app_exit.write(AppExit::Success);
+        }
+        MenuButton::Back => {
+            if let Some(previous) = menu_screen.and_then(|menu_screen| menu_screen.previous()) {
+                next_menu_screen.set(previous);
+            }
+        }
+    }
+}
+
+fn go_back(
+    back: On<Start<Back>>,
+    ui_contexts: Query<(), With<UiContext>>,
+    menu_screen: Option<Res<State<MenuScreen>>>,
+    mut next_menu_screen: ResMut<NextState<MenuScreen>>,
+) {
+    // Other contexts, like the knot puzzle, bind their own `Back`.
+    if !ui_contexts.contains(back.context) {
+        return;
+    }
+
+    if let Some(previous) = menu_screen.and_then(|menu_screen| menu_screen.previous()) {
+        next_menu_screen.set(previous);
+    }
+}
@@ -1,11 +1,23 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, ui_widgets::UiWidgetsPlugins};
 
+mod loading;
+mod main_menu;
 pub mod navigation;
+mod pause_menu;
+mod settings;
+mod widgets;
 
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut bevy::app::App) {
-        app.add_plugins((navigation::UiNavigationPlugin,));
+        app.add_plugins((
+            UiWidgetsPlugins,
+            loading::LoadingScreenPlugin,
+            main_menu::MainMenuPlugin,
+            navigation::UiNavigationPlugin,
+            pause_menu::PauseMenuPlugin,
+            settings::SettingsPlugin,
+        ));
     }
 }
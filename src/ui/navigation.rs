@@ -3,8 +3,8 @@ use bevy::{
     math::CompassOctant,
     picking::hover::Hovered,
     prelude::*,
-    ui::auto_directional_navigation::AutoDirectionalNavigator,
-    ui_widgets::Activate,
+    ui::auto_directional_navigation::{AutoDirectionalNavigation, AutoDirectionalNavigator},
+    ui_widgets::{Activate, SetSliderValue, Slider, SliderValueChange},
 };
 use bevy_enhanced_input::prelude::Start;
 
@@ -16,8 +16,11 @@ impl Plugin for UiNavigationPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            highlight_focused_element
-                .run_if(resource_changed::<InputFocus>.or(resource_changed::<InputFocusVisible>)),
+            highlight_focused_element.run_if(
+                resource_changed::<InputFocus>
+                    .or(resource_changed::<InputFocusVisible>)
+                    .or(any_match_filter::<Changed<Hovered>>),
+            ),
         );
 
         app.add_observer(navigate);
@@ -29,27 +32,57 @@ impl Plugin for UiNavigationPlugin {
 #[derive(Component)]
 pub struct FirstNavigableNode;
 
+/// Outlines the focused node, and any node under the mouse. Outlines are used rather than borders
+/// so nodes keep their own border styles.
 fn highlight_focused_element(
+    mut commands: Commands,
     input_focus: Res<InputFocus>,
     input_focus_visible: Res<InputFocusVisible>,
-    mut query: Query<(Entity, &mut BorderColor), With<Hovered>>,
+    navigable_nodes: Query<(Entity, Option<&Hovered>), With<AutoDirectionalNavigation>>,
 ) {
-    const FOCUSED_BORDER_COLOR: Color = Color::BLACK;
+    const FOCUSED_OUTLINE_COLOR: Color = Color::srgb(0.95, 0.85, 0.55);
 
-    for (entity, mut border_color) in query.iter_mut() {
-        todo!("Update highlighting style of focused element");
-        if input_focus.0 == Some(entity) && input_focus_visible.0 {
-            *border_color = BorderColor::all(FOCUSED_BORDER_COLOR);
+    for (entity, hovered) in &navigable_nodes {
+        let focused = input_focus.0 == Some(entity) && input_focus_visible.0;
+
+        if focused || hovered.is_some_and(Hovered::get) {
+            commands.entity(entity).insert(Outline::new(
+                Val::Px(2.0),
+                Val::Px(2.0),
+                FOCUSED_OUTLINE_COLOR,
+            ));
         } else {
-            *border_color = BorderColor::DEFAULT;
+            commands.entity(entity).remove::<Outline>();
         }
     }
 }
 
-fn navigate(navigate: On<Start<Navigate>>, mut navigator: AutoDirectionalNavigator) {
-    if let Some(direction) = Dir2::new(navigate.value).ok().map(CompassOctant::from) {
-        let _ = navigator.navigate(direction);
+fn navigate(
+    navigate: On<Start<Navigate>>,
+    mut commands: Commands,
+    sliders: Query<(), With<Slider>>,
+    // The navigator holds on to the focus to move it, so the two take turns.
+    mut focus_and_navigator: ParamSet<(Res<InputFocus>, AutoDirectionalNavigator)>,
+) {
+    let Ok(direction) = Dir2::new(navigate.value) else {
+        return;
+    };
+
+    // Left and right adjust a focused slider instead of leaving it.
+    if direction.x.abs() > direction.y.abs()
+        && let Some(entity) = focus_and_navigator.p0().0
+        && sliders.contains(entity)
+    {
+        commands.trigger(SetSliderValue {
+            entity,
+            change: SliderValueChange::RelativeStep(direction.x.signum()),
+        });
+        return;
     }
+
+    let _ = focus_and_navigator
+        .p1()
+        .navigate(CompassOctant::from(direction));
 }
 
 fn select(_: On<Start<Select>>, input_focus: Res<InputFocus>, mut commands: Commands) {
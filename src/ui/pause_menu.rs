@@ -0,0 +1,123 @@
+use bevy::{prelude::*, ui_widgets::Activate};
+use bevy_enhanced_input::prelude::Start;
+
+use crate::{
+    audio::AudioMixer,
+    input::actions::ui::{Back, UiContext},
+    localization::LocalizedText,
+    pause::Pause,
+    state::AppState,
+};
+
+use super::{navigation::FirstNavigableNode, settings::settings_panel, widgets::text_button};
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(Pause::Menu), spawn_pause_menu);
+        app.add_systems(OnEnter(Pause::Settings), spawn_pause_settings);
+
+        app.add_observer(on_activate_pause_button);
+        app.add_observer(go_back);
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+enum PauseButton {
+    Resume,
+    Settings,
+    ReturnToMenu,
+    Back,
+}
+
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
+
+fn spawn_pause_menu(mut commands: Commands) {
+    commands
+        .spawn(pause_overlay(Pause::Menu, "pause.title"))
+        .with_children(|parent| {
+            parent.spawn((
+                PauseButton::Resume,
+                text_button("pause.resume"),
+                FirstNavigableNode,
+            ));
+            parent.spawn((PauseButton::Settings, text_button("menu.settings")));
+            parent.spawn((
+                PauseButton::ReturnToMenu,
+                text_button("pause.return_to_menu"),
+            ));
+        });
+}
+
+fn spawn_pause_settings(mut commands: Commands, mixer: Res<AudioMixer>) {
+    commands
+        .spawn(pause_overlay(Pause::Settings, "settings.title"))
+        .with_children(|parent| {
+            parent.spawn(settings_panel(&mixer));
+            parent.spawn((PauseButton::Back, text_button("menu.back")));
+        });
+}
+
+/// Dims the game behind a column holding one screen of the pause menu.
+fn pause_overlay(screen: Pause, title_key: &str) -> impl Bundle {
+    (
+        DespawnOnExit(screen),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        BackgroundColor(OVERLAY_COLOR),
+        // Above the dialogue box and the knot puzzle, which may be open underneath.
+        GlobalZIndex(1),
+        children![(
+            LocalizedText::new(title_key),
+            TextFont::from_font_size(32.0),
+            Node {
+                margin: UiRect::bottom(Val::Px(24.0)),
+                ..default()
+            },
+        )],
+    )
+}
+
+fn on_activate_pause_button(
+    activate: On<Activate>,
+    pause_buttons: Query<&PauseButton>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    let Ok(button) = pause_buttons.get(activate.entity) else {
+        return;
+    };
+
+    match button {
+        PauseButton::Resume => next_pause.set(Pause::Running),
+        PauseButton::Settings => next_pause.set(Pause::Settings),
+        PauseButton::ReturnToMenu => next_app_state.set(AppState::MainMenu),
+        PauseButton::Back => next_pause.set(Pause::Menu),
+    }
+}
+
+fn go_back(
+    back: On<Start<Back>>,
+    ui_contexts: Query<(), With<UiContext>>,
+    pause: Option<Res<State<Pause>>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+) {
+    // Other contexts, like the knot puzzle, bind their own `Back`.
+    if !ui_contexts.contains(back.context) {
+        return;
+    }
+
+    match pause.as_deref().map(State::get) {
+        Some(Pause::Menu) => next_pause.set(Pause::Running),
+        Some(Pause::Settings) => next_pause.set(Pause::Menu),
+        Some(Pause::Running) | None => {}
+    }
+}
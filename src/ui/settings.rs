@@ -0,0 +1,248 @@
+use bevy::{
+    picking::hover::Hovered,
+    prelude::*,
+    ui::auto_directional_navigation::AutoDirectionalNavigation,
+    ui_widgets::{
+        Activate, Slider, SliderRange, SliderStep, SliderThumb, SliderValue, TrackClick,
+        ValueChange,
+    },
+};
+
+use crate::{
+    audio::{AudioBus, AudioMixer, BusSettings},
+    localization::{Locale, LocalizedText},
+};
+
+use super::{navigation::FirstNavigableNode, widgets::text_button};
+
+/// How much a volume slider moves per step of the keyboard or gamepad.
+const VOLUME_STEP: f32 = 0.05;
+const SLIDER_WIDTH: f32 = 200.0;
+const SLIDER_THUMB_WIDTH: f32 = 12.0;
+const SLIDER_TRACK_COLOR: Color = Color::srgb(0.35, 0.32, 0.28);
+const SLIDER_THUMB_COLOR: Color = Color::srgb(0.85, 0.8, 0.7);
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                sync_mixer_widgets.run_if(resource_changed::<AudioMixer>),
+                move_slider_thumbs,
+            ),
+        );
+
+        app.add_observer(on_activate_settings_button);
+        app.add_observer(on_change_volume);
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+enum SettingsButton {
+    Language,
+    Mute(AudioBus),
+}
+
+#[derive(Component, Clone, Copy)]
+struct VolumeSlider(AudioBus);
+
+/// The settings themselves, shared by every screen that shows them. The first setting takes the
+/// initial focus.
+pub fn settings_panel(mixer: &AudioMixer) -> impl Bundle + use<> {
+    let buses = AudioBus::ALL.map(|bus| (bus, mixer.bus(bus)));
+
+    (
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Start,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        Children::spawn((
+            Spawn(setting_row(
+                "settings.language",
+                (
+                    SettingsButton::Language,
+                    text_button("language.name"),
+                    FirstNavigableNode,
+                ),
+            )),
+            SpawnIter(
+                buses
+                    .into_iter()
+                    .map(|(bus, settings)| volume_row(bus, settings)),
+            ),
+        )),
+    )
+}
+
+/// A labelled row of the settings panel. Labels share a width so the widgets line up.
+fn setting_row(label_key: &'static str, widget: impl Bundle) -> impl Bundle {
+    (
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(16.0),
+            ..default()
+        },
+        children![
+            (
+                LocalizedText::new(label_key),
+                TextFont::from_font_size(18.0),
+                Node {
+                    width: Val::Px(120.0),
+                    ..default()
+                },
+            ),
+            widget,
+        ],
+    )
+}
+
+fn volume_row(bus: AudioBus, settings: BusSettings) -> impl Bundle {
+    setting_row(
+        bus.label_key(),
+        (
+            Node {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(16.0),
+                ..default()
+            },
+            children![
+                volume_slider(bus, settings.volume),
+                (
+                    SettingsButton::Mute(bus),
+                    text_button(mute_label_key(settings.muted)),
+                ),
+            ],
+        ),
+    )
+}
+
+fn mute_label_key(muted: bool) -> &'static str {
+    if muted {
+        "settings.muted"
+    } else {
+        "settings.unmuted"
+    }
+}
+
+/// A horizontal slider from silent to full volume. Left and right move it while it's focused.
+fn volume_slider(bus: AudioBus, volume: f32) -> impl Bundle {
+    (
+        VolumeSlider(bus),
+        Slider {
+            track_click: TrackClick::Snap,
+        },
+        SliderValue(volume),
+        SliderRange::new(0.0, 1.0),
+        SliderStep(VOLUME_STEP),
+        Hovered::default(),
+        AutoDirectionalNavigation::default(),
+        Node {
+            width: Val::Px(SLIDER_WIDTH),
+            height: Val::Px(20.0),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![
+            (
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(4.0),
+                    ..default()
+                },
+                BackgroundColor(SLIDER_TRACK_COLOR),
+            ),
+            // The thumb moves within the track minus its own width, so it never sticks out.
+            (
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    right: Val::Px(SLIDER_THUMB_WIDTH),
+                    top: Val::Px(0.0),
+                    bottom: Val::Px(0.0),
+                    ..default()
+                },
+                children![(
+                    SliderThumb,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Percent(volume * 100.0),
+                        width: Val::Px(SLIDER_THUMB_WIDTH),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(SLIDER_THUMB_COLOR),
+                )],
+            ),
+        ],
+    )
+}
+
+fn on_activate_settings_button(
+    activate: On<Activate>,
+    settings_buttons: Query<&SettingsButton>,
+    mut locale: ResMut<Locale>,
+    mut mixer: ResMut<AudioMixer>,
+) {
+    let Ok(button) = settings_buttons.get(activate.entity) else {
+        return;
+    };
+
+    match button {
+        SettingsButton::Language => *locale = locale.next(),
+        SettingsButton::Mute(bus) => mixer.toggle_mute(*bus),
+    }
+}
+
+/// Sliders only report where they were moved to, and the mixer decides what they show.
+fn on_change_volume(
+    value_change: On<ValueChange<f32>>,
+    volume_sliders: Query<&VolumeSlider>,
+    mut mixer: ResMut<AudioMixer>,
+) {
+    if let Ok(VolumeSlider(bus)) = volume_sliders.get(value_change.source) {
+        mixer.set_volume(*bus, value_change.value);
+    }
+}
+
+fn sync_mixer_widgets(
+    mixer: Res<AudioMixer>,
+    mut volume_sliders: Query<(&VolumeSlider, &mut SliderValue)>,
+    settings_buttons: Query<(&SettingsButton, &Children)>,
+    mut labels: Query<&mut LocalizedText>,
+) {
+    for (VolumeSlider(bus), mut slider_value) in &mut volume_sliders {
+        slider_value.set_if_neq(SliderValue(mixer.bus(*bus).volume));
+    }
+
+    for (button, children) in &settings_buttons {
+        let SettingsButton::Mute(bus) = button else {
+            continue;
+        };
+        let key = mute_label_key(mixer.bus(*bus).muted);
+        let mut labels = labels.iter_many_mut(children);
+
+        while let Some(mut label) = labels.fetch_next() {
+            if label.0 != key {
+                label.0 = key.to_string();
+            }
+        }
+    }
+}
+
+fn move_slider_thumbs(
+    sliders: Query<(Entity, &SliderValue, &SliderRange), Changed<SliderValue>>,
+    children: Query<&Children>,
+    mut thumbs: Query<&mut Node, With<SliderThumb>>,
+) {
+    for (slider, value, range) in &sliders {
+        for descendant in children.iter_descendants(slider) {
+            if let Ok(mut node) = thumbs.get_mut(descendant) {
+                node.left = Val::Percent(range.thumb_position(value.0) * 100.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::{
+    picking::hover::Hovered, prelude::*,
+    ui::auto_directional_navigation::AutoDirectionalNavigation, ui_widgets::Button,
+};
+
+use crate::localization::LocalizedText;
+
+const BUTTON_BORDER_COLOR: Color = Color::srgb(0.6, 0.55, 0.45);
+
+/// A bordered button labelled with a localized string. It can be focused with the keyboard or a
+/// gamepad and clicked with the mouse, and triggers `Activate` either way.
+pub fn text_button(label_key: &str) -> impl Bundle {
+    (
+        Button,
+        Hovered::default(),
+        AutoDirectionalNavigation::default(),
+        Node {
+            min_width: Val::Px(160.0),
+            padding: UiRect::axes(Val::Px(16.0), Val::Px(6.0)),
+            border: UiRect::all(Val::Px(1.0)),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BorderColor::all(BUTTON_BORDER_COLOR),
+        children![(
+            LocalizedText::new(label_key),
+            TextFont::from_font_size(20.0)
+        )],
+    )
+}